@@ -0,0 +1,103 @@
+//! Times the spatial hash against testing every pair of entities, for crowds of small
+//! asteroids, along with the whole of `find_collisions`. Run with
+//! `cargo bench --bench collisions`.
+
+extern crate asteroids;
+extern crate cgmath;
+extern crate rand;
+extern crate time;
+
+use asteroids::Entity;
+use asteroids::EntityState;
+use asteroids::collisions;
+use asteroids::spatial_hash::SpatialHash;
+use asteroids::tunables::Tunables;
+use cgmath::Vector4;
+use rand::Rng;
+use rand::SeedableRng;
+use rand::XorShiftRng;
+
+const COUNTS: [usize; 2] = [1_000, 10_000];
+const RUNS: u64 = 5;
+
+/// `count` small asteroids scattered over the world.
+fn crowd(count: usize) -> (EntityState, Vec<Entity>) {
+    let mut rng = XorShiftRng::from_seed([1, 2, 3, 4]);
+    let tunables = Tunables::new();
+    let mut state = EntityState::new(1);
+    let entities = (0..count)
+                       .map(|_| {
+                           let position = Vector4::new(rng.next_f32() * 800.0,
+                                                       rng.next_f32() * 600.0,
+                                                       0.0,
+                                                       1.0);
+                           Entity::small_asteroid(&mut state, &tunables, position)
+                       })
+                       .collect();
+    (state, entities)
+}
+
+fn overlap(a: (f32, f32, f32), b: (f32, f32, f32)) -> bool {
+    let (dx, dy) = (b.0 - a.0, b.1 - a.1);
+    dx * dx + dy * dy < (a.2 + b.2) * (a.2 + b.2)
+}
+
+/// The number of overlapping circles, testing every pair the way `find_collisions` did before
+/// it had a broad phase.
+fn every_pair(circles: &[(f32, f32, f32)]) -> usize {
+    let mut count = 0;
+    for (i, &a) in circles.iter().enumerate() {
+        for &b in &circles[i + 1..] {
+            if overlap(a, b) {
+                count += 1;
+            }
+        }
+    }
+    count
+}
+
+/// The number of overlapping circles, testing only the pairs from a spatial hash.
+fn hashed(circles: &[(f32, f32, f32)]) -> usize {
+    let widest = circles.iter().fold(0.0, |widest: f32, c| widest.max(c.2 * 2.0));
+    let mut grid = SpatialHash::new(800.0, 600.0, widest);
+    for (index, &(x, y, _)) in circles.iter().enumerate() {
+        grid.insert(index, x, y);
+    }
+    grid.pairs().into_iter().filter(|&(i, j)| overlap(circles[i], circles[j])).count()
+}
+
+/// Average milliseconds per run of `f`, and what it returned.
+fn time<F: Fn() -> usize>(f: F) -> (f64, usize) {
+    let start = time::precise_time_ns();
+    let mut result = 0;
+    for _ in 0..RUNS {
+        result = f();
+    }
+    ((time::precise_time_ns() - start) as f64 / RUNS as f64 / 1_000_000.0, result)
+}
+
+fn main() {
+    for &count in &COUNTS {
+        let (state, entities) = crowd(count);
+        let circles = entities.iter()
+                              .map(|e| {
+                                  let position = state.positions.get(&e.id).unwrap();
+                                  let radius = state.scales.get(&e.id).unwrap().x / 2.0;
+                                  (position.x, position.y, radius)
+                              })
+                              .collect::<Vec<_>>();
+        let (naive, expected) = time(|| every_pair(&circles));
+        let (grid, found) = time(|| hashed(&circles));
+        assert_eq!(found, expected, "The spatial hash missed some pairs.");
+        let (whole, collisions) = time(|| collisions::find_collisions(&state, &entities).len());
+        println!("{:>6} entities: every pair {:>9.3} ms, spatial hash {:>9.3} ms, {:.1}x faster",
+                 count,
+                 naive,
+                 grid,
+                 naive / grid);
+        println!("{:>6} entities: find_collisions {:>9.3} ms for {} collisions",
+                 count,
+                 whole,
+                 collisions);
+    }
+}
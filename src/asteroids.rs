@@ -1,118 +1,414 @@
 extern crate cgmath;
-extern crate gl;
 extern crate time;
 
 use cgmath::Matrix4;
 use cgmath::Matrix;
+use cgmath::Vector4;
 use cgmath::SquareMatrix;
 use cgmath::Vector;
-use std::collections::HashMap;
-use std::collections::HashSet;
+use std::collections::BTreeSet;
+use std::fmt::Write;
 use super::collisions;
 use super::entity::Entity;
 use super::entity::EntityState;
 use super::entity::Kind;
+use super::entity::SaucerSize;
 use super::entity::Size;
-
-enum InputStatus {
-    Up,
-    Down,
-}
+use super::factions;
+use super::font;
+use super::factions::Effect;
+use super::factions::Faction;
+use super::input::Action;
+use super::input::Actions;
+use super::input::Input;
+use super::models::Model;
+use super::physics;
+use super::physics::Handling;
+use super::physics::Integrator;
+use super::render;
+use super::render::DrawCommand;
+use super::render::Renderer;
+use super::snapshot::Record;
+use super::tunables::Tunables;
 
 pub struct Asteroids {
     should_continue: bool,
+    paused: bool,
     stage: u32,
     // TODO: Should some of these be in entity state instead?
     score: u32,
     lives: u32,
     live_up: u32,
     invulnerability_time: f32,
+    // Seconds until the next saucer, which waits for the previous one to leave
+    saucer_time: f32,
+    tunables: Tunables,
+    handling: Handling,
     projection: Matrix4<f32>,
     entities: Vec<Entity>,
     state: EntityState,
-    input: HashMap<char, InputStatus>,
+    // Actions that were down at the end of the previous tick
+    held: BTreeSet<Action>,
 }
 
 impl Asteroids {
-    pub fn new() -> Asteroids {
-        let entity_state = EntityState::new();
+    pub fn new(seed: u64) -> Asteroids {
+        Asteroids::with_tunables(seed, Tunables::new())
+    }
+
+    pub fn with_tunables(seed: u64, tunables: Tunables) -> Asteroids {
+        let entity_state = EntityState::new(seed);
         Asteroids {
             should_continue: true,
+            paused: false,
             stage: 1,
             score: 0,
-            lives: 3,
+            lives: tunables.starting_lives,
             live_up: 0,
             invulnerability_time: 0.0,
+            saucer_time: tunables.saucer_interval,
+            tunables: tunables,
+            handling: Handling::Classic,
             projection: cgmath::ortho(0.0, 800.0, 600.0, 0.0, -1.0, 1.0),
             entities: Vec::new(),
             state: entity_state,
-            input: HashMap::new(),
+            held: BTreeSet::new(),
         }
     }
 
     pub fn should_continue(&self) -> bool {
         self.should_continue
     }
+
+    pub fn paused(&self) -> bool {
+        self.paused
+    }
+
+    /// Every live entity, with the player ship first.
+    pub fn entities(&self) -> &[Entity] {
+        &self.entities
+    }
+
+    pub fn state(&self) -> &EntityState {
+        &self.state
+    }
+
+    /// The components of every entity, to set up a game directly. Every entity in the list
+    /// must keep the components `EntityState::is_complete` checks for.
+    pub fn state_mut(&mut self) -> &mut EntityState {
+        &mut self.state
+    }
+
+    /// Moves the ship to `position` going at `velocity`, as if it had been there all along.
+    /// Does nothing before the ship has spawned.
+    pub fn place_ship(&mut self, position: Vector4<f32>, velocity: Vector4<f32>) {
+        if let Some(ship) = self.entities.first() {
+            self.state.positions.insert(ship.id, position);
+            self.state.previous_positions.insert(ship.id, position);
+            self.state.velocities.insert(ship.id, velocity);
+        }
+    }
+
+    pub fn stage(&self) -> u32 {
+        self.stage
+    }
+
+    pub fn score(&self) -> u32 {
+        self.score
+    }
+
+    pub fn lives(&self) -> u32 {
+        self.lives
+    }
+
+    pub fn tunables(&self) -> &Tunables {
+        &self.tunables
+    }
+
+    /// Swaps in new tunables in the middle of a game, from the next tick on. Whatever is read
+    /// every tick, like the turn rate and saucer speed, changes straight away, but entities
+    /// already in play keep the speed and lifetime they were spawned with, and the lives left
+    /// stay as they are.
+    pub fn set_tunables(&mut self, tunables: Tunables) {
+        self.tunables = tunables;
+    }
+
+    pub fn integrator(&self) -> Integrator {
+        self.state.integrator
+    }
+
+    pub fn set_integrator(&mut self, integrator: Integrator) {
+        self.state.integrator = integrator;
+    }
+
+    pub fn handling(&self) -> Handling {
+        self.handling
+    }
+
+    /// Changes how the ship handles, including a ship that is already flying.
+    pub fn set_handling(&mut self, handling: Handling) {
+        self.handling = handling;
+        for entity in &self.entities {
+            if let Some(&Kind::PlayerShip) = self.state.kinds.get(&entity.id) {
+                self.state.drags.insert(entity.id, handling.drag());
+                self.state.max_speeds.insert(entity.id, handling.max_speed());
+            }
+        }
+    }
+
+    /// Maps a point in a window of `width` by `height` pixels, y pointing down, to world
+    /// coordinates through the inverse of the projection, so it holds for any window size.
+    pub fn window_to_world(&self, x: f32, y: f32, width: f32, height: f32) -> (f32, f32) {
+        let (clip_x, clip_y) = (2.0 * x / width - 1.0, 1.0 - 2.0 * y / height);
+        // Matrix4::invert mistakes the projection's tiny determinant for zero, but the
+        // projection is affine and keeps z out of x and y, so inverting its 2x2 part will do
+        let p = &self.projection;
+        let (a, b, c, d) = (p[0][0], p[1][0], p[0][1], p[1][1]);
+        let (x, y) = (clip_x - p[3][0], clip_y - p[3][1]);
+        let determinant = a * d - b * c;
+        ((d * x - b * y) / determinant, (a * y - c * x) / determinant)
+    }
+
+    /// FNV-1a hash of the gameplay state, used to check that a replay reproduced the same game.
+    pub fn checksum(&self) -> u64 {
+        let mut hash = FNV_OFFSET_BASIS;
+        hash = fnv_u32(hash, self.stage);
+        hash = fnv_u32(hash, self.score);
+        hash = fnv_u32(hash, self.lives);
+        hash = fnv_u32(hash, self.live_up);
+        hash = fnv_u32(hash, self.invulnerability_time.to_bits());
+        hash = fnv_u32(hash, self.saucer_time.to_bits());
+        hash = fnv_u32(hash, self.entities.len() as u32);
+        for entity in &self.entities {
+            let kind = match *self.state.kinds.get(&entity.id).unwrap() {
+                Kind::PlayerShip => 0,
+                Kind::ProjectileFriendly => 1,
+                Kind::Asteroid(Size::Large) => 2,
+                Kind::Asteroid(Size::Medium) => 3,
+                Kind::Asteroid(Size::Small) => 4,
+                Kind::Saucer(SaucerSize::Large) => 5,
+                Kind::Saucer(SaucerSize::Small) => 6,
+                Kind::ProjectileHostile => 7,
+            };
+            let position = self.state.positions.get(&entity.id).unwrap();
+            let velocity = self.state.velocities.get(&entity.id).unwrap();
+            let direction = self.state.directions.get(&entity.id).unwrap();
+            hash = fnv_u32(hash, entity.id);
+            hash = fnv_u32(hash, kind);
+            hash = fnv_u32(hash, position.x.to_bits());
+            hash = fnv_u32(hash, position.y.to_bits());
+            hash = fnv_u32(hash, velocity.x.to_bits());
+            hash = fnv_u32(hash, velocity.y.to_bits());
+            hash = fnv_u32(hash, direction.to_bits());
+        }
+        hash
+    }
+
+    pub fn write_snapshot(&self, out: &mut String) {
+        writeln!(out, "stage {}", self.stage).unwrap();
+        writeln!(out, "score {}", self.score).unwrap();
+        writeln!(out, "lives {}", self.lives).unwrap();
+        writeln!(out, "live_up {}", self.live_up).unwrap();
+        writeln!(out, "invulnerability_time {:?}", self.invulnerability_time).unwrap();
+        writeln!(out, "saucer_time {:?}", self.saucer_time).unwrap();
+        for (name, value) in self.tunables.pairs() {
+            writeln!(out, "tunable {} {}", name, value).unwrap();
+        }
+        writeln!(out, "handling {}", self.handling.name()).unwrap();
+        writeln!(out, "paused {}", self.paused).unwrap();
+        let held = self.held.iter().map(|a| a.name()).collect::<Vec<_>>();
+        writeln!(out, "held {}", held.join(" ")).unwrap();
+        let ids = self.entities.iter().map(|e| e.id.to_string()).collect::<Vec<_>>();
+        writeln!(out, "entities {}", ids.join(" ")).unwrap();
+        self.state.write_snapshot(out);
+    }
+
+    pub fn read_snapshot(records: &[Record]) -> Result<Asteroids, String> {
+        let mut asteroids = Asteroids::new(0);
+        for record in records {
+            match record.name {
+                "stage" => asteroids.stage = record.get(0)?,
+                "score" => asteroids.score = record.get(0)?,
+                "lives" => asteroids.lives = record.get(0)?,
+                "live_up" => asteroids.live_up = record.get(0)?,
+                "invulnerability_time" => asteroids.invulnerability_time = record.get(0)?,
+                "saucer_time" => asteroids.saucer_time = record.get(0)?,
+                "tunable" => {
+                    asteroids.tunables.set(record.get_str(0)?, record.get_str(1)?)?;
+                }
+                "handling" => {
+                    asteroids.handling = Handling::from_name(record.get_str(0)?)
+                                             .ok_or(record.unknown())?;
+                }
+                "paused" => asteroids.paused = record.get(0)?,
+                "held" => {
+                    let mut index = 0;
+                    while let Ok(name) = record.get_str(index) {
+                        let action = Action::from_name(name).ok_or(record.unknown())?;
+                        asteroids.held.insert(action);
+                        index += 1;
+                    }
+                }
+                "entities" => {
+                    let mut index = 0;
+                    while let Ok(id) = record.get_str(index) {
+                        let id = id.parse().map_err(|_| record.unknown())?;
+                        asteroids.entities.push(Entity { id: id });
+                        index += 1;
+                    }
+                }
+                _ => asteroids.state.read_record(record)?,
+            }
+        }
+        asteroids.tunables.validate()?;
+        // The game steers the first entity as the ship
+        if let Some(ship) = asteroids.entities.first() {
+            match asteroids.state.kinds.get(&ship.id) {
+                Some(&Kind::PlayerShip) | None => (),
+                Some(_) => return Err("The first entity in snapshot is not the ship.".to_string()),
+            }
+        }
+        for entity in &asteroids.entities {
+            if !asteroids.state.is_complete(entity.id) {
+                return Err(format!("Entity {} in snapshot is missing components.", entity.id));
+            }
+        }
+        Ok(asteroids)
+    }
 }
 
-pub fn update_and_render(asteroids: &mut Asteroids, input: &HashMap<char, u32>, dt: f32) {
+const FNV_OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+const FNV_PRIME: u64 = 0x100000001b3;
+
+fn fnv_u32(mut hash: u64, value: u32) -> u64 {
+    for byte in 0..4 {
+        hash ^= ((value >> (byte * 8)) & 0xff) as u64;
+        hash = hash.wrapping_mul(FNV_PRIME);
+    }
+    hash
+}
+
+pub fn update(asteroids: &mut Asteroids, input: &Input, dt: f32) {
+    let actions = Actions::new(&asteroids.held, input);
+    asteroids.held = input.down.clone();
+
+    if actions.pressed(Action::Quit) {
+        asteroids.should_continue = false;
+        return;
+    }
+
+    // Remember where everything was so rendering can interpolate towards this tick
+    asteroids.state.previous_positions = asteroids.state.positions.clone();
+    asteroids.state.previous_directions = asteroids.state.directions.clone();
+
+    if actions.pressed(Action::Pause) {
+        asteroids.paused = !asteroids.paused;
+    }
+    if asteroids.paused {
+        return;
+    }
+
     if asteroids.entities.is_empty() {
-        asteroids.entities.push(Entity::player_ship(&mut asteroids.state));
+        asteroids.entities.push(Entity::player_ship(&mut asteroids.state, asteroids.handling));
     } else if asteroids.entities.len() == 1 {
         for _ in 1..asteroids.stage {
-            asteroids.entities.push(Entity::large_asteroid(&mut asteroids.state));
-            asteroids.entities.push(Entity::large_asteroid(&mut asteroids.state));
-            asteroids.entities.push(Entity::large_asteroid(&mut asteroids.state));
+            let tunables = &asteroids.tunables;
+            asteroids.entities.push(Entity::large_asteroid(&mut asteroids.state, tunables));
+            asteroids.entities.push(Entity::large_asteroid(&mut asteroids.state, tunables));
+            asteroids.entities.push(Entity::large_asteroid(&mut asteroids.state, tunables));
         }
         asteroids.stage += 1;
     }
 
-    for (&event, &transition_count) in input {
-        if transition_count % 2 == 0 {
-            let status = asteroids.input.entry(event).or_insert(InputStatus::Up);
-            match *status {
-                InputStatus::Up => *status = InputStatus::Down,
-                InputStatus::Down => *status = InputStatus::Up,
-            }
-        }
-    }
-
     let mut projectiles = 0;
+    let mut jump = false;
     {
         let entity_id = asteroids.entities[0].id;
-        let direction = asteroids.state.directions.get_mut(&entity_id).unwrap();
+        let position = asteroids.state.positions.get(&entity_id).unwrap();
+        let direction = asteroids.state.directions.get(&entity_id).unwrap();
+        let angular_velocity = asteroids.state.angular_velocities.get_mut(&entity_id).unwrap();
         let acceleration = asteroids.state.accelerations.get_mut(&entity_id).unwrap();
         let weapon_cooldown = asteroids.state.weapon_cooldowns.get(&entity_id).unwrap();
-        for ins in &asteroids.input {
-            match ins {
-                (&'w', &InputStatus::Down) => {
-                    acceleration.x += cgmath::sin(cgmath::deg(*direction));
-                    acceleration.y += -cgmath::cos(cgmath::deg(*direction));
-                }
-                (&'a', &InputStatus::Down) => *direction += -5.0,
-                (&'d', &InputStatus::Down) => *direction += 5.0,
-                (&' ', &InputStatus::Down) => {
-                    if *weapon_cooldown <= 0.0 {
-                        projectiles += 1;
-                    }
-                }
-                (&'q', &InputStatus::Down) => {
-                    asteroids.should_continue = false;
-                    return;
+        let hyperspace_cooldown = asteroids.state.hyperspace_cooldowns.get(&entity_id).unwrap();
+        let jumping = asteroids.state.jump_times.contains_key(&entity_id);
+        let turn_rate = asteroids.tunables.turn_rate;
+        *angular_velocity = 0.0;
+        // The ship can not be steered while it is in hyperspace
+        if !jumping {
+            if actions.held(Action::RotateLeft) {
+                *angular_velocity -= turn_rate;
+            }
+            if actions.held(Action::RotateRight) {
+                *angular_velocity += turn_rate;
+            }
+            if let Some((x, y)) = input.aim {
+                // Turn the short way round towards the target, no faster than the keys turn,
+                // and without overshooting it within the tick
+                let target = (x - position.x).atan2(position.y - y).to_degrees();
+                let turn = ((target - *direction) % 360.0 + 540.0) % 360.0 - 180.0;
+                if dt > 0.0 {
+                    *angular_velocity += (turn / dt).max(-turn_rate).min(turn_rate);
                 }
-                _ => (),
+            }
+            if actions.held(Action::Thrust) {
+                let thrust = asteroids.handling.thrust();
+                acceleration.x += cgmath::sin(cgmath::deg(*direction)) * thrust;
+                acceleration.y += -cgmath::cos(cgmath::deg(*direction)) * thrust;
+            }
+            if actions.held(Action::Fire) && *weapon_cooldown <= 0.0 {
+                projectiles += 1;
+            }
+            if actions.pressed(Action::Hyperspace) && *hyperspace_cooldown <= 0.0 {
+                jump = true;
             }
         }
-
     }
 
     if projectiles > 0 {
         let entity_id = asteroids.entities[0].id;
         let position = asteroids.state.positions.get(&entity_id).unwrap().clone();
         let direction = asteroids.state.directions.get(&entity_id).unwrap().clone();
-        asteroids.entities.push(Entity::projectile(&mut asteroids.state, position, direction));
+        asteroids.entities.push(Entity::projectile(&mut asteroids.state,
+                                                   &asteroids.tunables,
+                                                   position,
+                                                   direction));
         let weapon_cooldown = asteroids.state.weapon_cooldowns.get_mut(&entity_id).unwrap();
-        *weapon_cooldown = 0.2;
+        *weapon_cooldown = asteroids.tunables.weapon_cooldown;
+    }
+
+    if jump {
+        asteroids.entities[0].jump(&mut asteroids.state, &asteroids.tunables);
+    }
+
+    // Send in a saucer every so often, more often in later stages
+    let saucers = asteroids.entities
+                           .iter()
+                           .filter(|e| match *asteroids.state.kinds.get(&e.id).unwrap() {
+                               Kind::Saucer(_) => true,
+                               _ => false,
+                           })
+                           .cloned()
+                           .collect::<Vec<_>>();
+    asteroids.saucer_time -= dt;
+    if asteroids.saucer_time <= 0.0 && saucers.is_empty() {
+        let tunables = &asteroids.tunables;
+        let small_chance = tunables.small_saucer_chance * asteroids.stage as f32;
+        asteroids.entities.push(Entity::saucer(&mut asteroids.state, tunables, small_chance));
+        asteroids.saucer_time = (tunables.saucer_interval -
+                                 tunables.saucer_interval_step * asteroids.stage as f32)
+                                    .max(tunables.saucer_min_interval);
+    }
+    let target = *asteroids.state.positions.get(&asteroids.entities[0].id).unwrap();
+    for saucer in saucers {
+        saucer.zigzag(&mut asteroids.state, &asteroids.tunables);
+        if *asteroids.state.weapon_cooldowns.get(&saucer.id).unwrap() <= 0.0 {
+            let tunables = &asteroids.tunables;
+            let spread = (tunables.small_saucer_spread -
+                          tunables.small_saucer_spread_step * asteroids.stage as f32)
+                             .max(0.0);
+            let shot = saucer.saucer_shot(&mut asteroids.state, tunables, target, spread);
+            asteroids.entities.push(shot);
+        }
     }
 
     asteroids.invulnerability_time -= dt;
@@ -121,65 +417,38 @@ pub fn update_and_render(asteroids: &mut Asteroids, input: &HashMap<char, u32>,
         entity.update(&mut asteroids.state, dt);
     }
 
-    // Remove entities whose lifetime has run out
-    let dead = asteroids.state
-                        .lifetimes
-                        .iter()
-                        .filter(|&(_, lifetime)| *lifetime <= 0.0)
-                        .map(|(id, _)| *id)
-                        .collect::<Vec<_>>();
-    asteroids.entities.retain(|e| !dead.contains(&e.id));
-    for id in dead {
-        asteroids.state.remove(id);
-    }
-
     // Collect all collisions
     let collisions = collisions::find_collisions(&asteroids.state, &asteroids.entities);
 
-    // Collect destroyed entities
-    let mut destroyed = HashSet::new();
-    for ((a, kind_a), (b, kind_b)) in collisions {
-        match (kind_a, kind_b) {
-            (Kind::PlayerShip, Kind::Asteroid(_)) => {
-                if asteroids.invulnerability_time >= 0.0 {
-                    ()
-                } else if asteroids.lives <= 1 {
-                    destroyed.insert(a);
-                } else {
-                    asteroids.lives -= 1;
-                    asteroids.invulnerability_time = 1.0;
-                }
-            }
-            (Kind::Asteroid(_), Kind::PlayerShip) => {
-                if asteroids.invulnerability_time >= 0.0 {
-                    ()
-                } else if asteroids.lives <= 1 {
-                    destroyed.insert(b);
-                } else {
-                    asteroids.lives -= 1;
-                    asteroids.invulnerability_time = 1.0;
+    // Collect destroyed entities, ordered so that fragments spawn deterministically
+    let mut destroyed = BTreeSet::new();
+    for collision in collisions {
+        // Each side of the collision gets what its layer gets from the other's
+        for &(id, kind, other) in &[(collision.a, collision.kind_a, collision.kind_b),
+                                    (collision.b, collision.kind_b, collision.kind_a)] {
+            match factions::effect(kind, other) {
+                Effect::Nothing => (),
+                Effect::Damage => {
+                    if asteroids.invulnerability_time < 0.0 {
+                        lose_life(asteroids, id, &mut destroyed);
+                    }
                 }
-            }
-            (Kind::Asteroid(s), Kind::ProjectileFriendly) |
-            (Kind::ProjectileFriendly, Kind::Asteroid(s)) => {
-                destroyed.insert(a);
-                destroyed.insert(b);
-                let points = match s {
-                    Size::Large => 10,
-                    Size::Medium => 25,
-                    Size::Small => 50,
-                };
-                // TODO: Score gets counted twice! Once for (a, b) and one for (b, a)
-                asteroids.score += points;
-                println!("Score: {}", asteroids.score);
-                asteroids.live_up += points;
-                // TODO: Verify that this is correct
-                if asteroids.live_up >= 2000 {
-                    asteroids.lives += 1;
-                    asteroids.live_up = asteroids.live_up % 2000;
+                Effect::Destroy => {
+                    // Scored once, however many shots hit it in the same tick
+                    if destroyed.insert(id) && factions::faction(other) == Faction::Player {
+                        let points = factions::points(kind, &asteroids.tunables);
+                        add_score(asteroids, points);
+                    }
                 }
             }
-            _ => (),
+        }
+    }
+
+    // Bring the ship back out of hyperspace once it has been gone long enough
+    let ship = asteroids.entities[0];
+    if asteroids.state.jump_times.get(&ship.id).map_or(false, |&time| time <= 0.0) {
+        if ship.reenter(&mut asteroids.state, &asteroids.tunables) {
+            lose_life(asteroids, ship.id, &mut destroyed);
         }
     }
 
@@ -189,13 +458,19 @@ pub fn update_and_render(asteroids: &mut Asteroids, input: &HashMap<char, u32>,
         match *asteroids.state.kinds.get(&d).unwrap() {
             Kind::Asteroid(Size::Large) => {
                 let position = *asteroids.state.positions.get(&d).unwrap();
-                asteroids.entities.push(Entity::medium_asteroid(&mut asteroids.state, position));
-                asteroids.entities.push(Entity::medium_asteroid(&mut asteroids.state, position));
+                let tunables = &asteroids.tunables;
+                asteroids.entities
+                         .push(Entity::medium_asteroid(&mut asteroids.state, tunables, position));
+                asteroids.entities
+                         .push(Entity::medium_asteroid(&mut asteroids.state, tunables, position));
             }
             Kind::Asteroid(Size::Medium) => {
                 let position = *asteroids.state.positions.get(&d).unwrap();
-                asteroids.entities.push(Entity::small_asteroid(&mut asteroids.state, position));
-                asteroids.entities.push(Entity::small_asteroid(&mut asteroids.state, position));
+                let tunables = &asteroids.tunables;
+                asteroids.entities
+                         .push(Entity::small_asteroid(&mut asteroids.state, tunables, position));
+                asteroids.entities
+                         .push(Entity::small_asteroid(&mut asteroids.state, tunables, position));
             }
             Kind::PlayerShip => asteroids.should_continue = false,
             _ => (),
@@ -203,39 +478,131 @@ pub fn update_and_render(asteroids: &mut Asteroids, input: &HashMap<char, u32>,
         asteroids.state.remove(d);
     }
 
-    unsafe {
-        gl::Clear(gl::COLOR_BUFFER_BIT);
+    // Remove entities whose lifetime has run out and saucers that have crossed the screen,
+    // once they have had their last chance to hit something on the way
+    let mut dead = asteroids.state
+                            .lifetimes
+                            .iter()
+                            .filter(|&(_, lifetime)| *lifetime <= 0.0)
+                            .map(|(id, _)| *id)
+                            .collect::<Vec<_>>();
+    for entity in &asteroids.entities {
+        if let Kind::Saucer(_) = *asteroids.state.kinds.get(&entity.id).unwrap() {
+            if entity.has_crossed(&asteroids.state) {
+                dead.push(entity.id);
+            }
+        }
+    }
+    asteroids.entities.retain(|e| !dead.contains(&e.id));
+    for id in dead {
+        asteroids.state.remove(id);
     }
+}
 
-    // Draw entities
-    for entity in &asteroids.entities {
-        let mut model = Matrix4::one();
+fn add_score(asteroids: &mut Asteroids, points: u32) {
+    asteroids.score += points;
+    asteroids.live_up += points;
+    // TODO: Verify that this is correct
+    let extra_life_points = asteroids.tunables.extra_life_points;
+    if extra_life_points > 0 && asteroids.live_up >= extra_life_points {
+        asteroids.lives += 1;
+        asteroids.live_up = asteroids.live_up % extra_life_points;
+    }
+}
 
-        let mut translation = Matrix4::one();
-        let position = asteroids.state.positions.get(&entity.id).unwrap();
-        translation.replace_col(3, *position);
-        model = model.mul_m(&translation);
+/// Takes a life for the ship being hit, or destroys it on its last life.
+fn lose_life(asteroids: &mut Asteroids, ship: u32, destroyed: &mut BTreeSet<u32>) {
+    if asteroids.lives <= 1 {
+        destroyed.insert(ship);
+    } else {
+        asteroids.lives -= 1;
+        asteroids.invulnerability_time = asteroids.tunables.invulnerability_time;
+    }
+}
+
+fn interpolated_position(state: &EntityState, id: u32, alpha: f32) -> Vector4<f32> {
+    let current = *state.positions.get(&id).unwrap();
+    match state.previous_positions.get(&id) {
+        Some(&previous) => {
+            // Go the short way round when the entity wrapped around an edge, leaving it partly
+            // off the screen for its ghost to show the rest
+            let d = current - previous;
+            let d = Vector4::new(physics::wrap_offset(d.x, 800.0),
+                                 physics::wrap_offset(d.y, 600.0),
+                                 0.0,
+                                 0.0);
+            current - d * (1.0 - alpha)
+        }
+        None => current,
+    }
+}
 
+fn interpolated_direction(state: &EntityState, id: u32, alpha: f32) -> f32 {
+    let current = *state.directions.get(&id).unwrap();
+    match state.previous_directions.get(&id) {
+        Some(&previous) => previous + (current - previous) * alpha,
+        None => current,
+    }
+}
+
+/// Where to draw an entity reaching `reach` around `position`: where it is, and again as a ghost
+/// on the opposite side of every edge it sticks out over, so it shows on both sides.
+fn ghost_offsets(position: Vector4<f32>, reach: f32) -> Vec<Vector4<f32>> {
+    let sides = |at: f32, size: f32| {
+        if at - reach < 0.0 {
+            vec![0.0, size]
+        } else if at + reach > size {
+            vec![0.0, -size]
+        } else {
+            vec![0.0]
+        }
+    };
+    let mut offsets = Vec::new();
+    for &y in &sides(position.y, 600.0) {
+        for &x in &sides(position.x, 800.0) {
+            offsets.push(Vector4::new(x, y, 0.0, 0.0));
+        }
+    }
+    offsets
+}
+
+/// Builds the draw commands for the game `alpha` of the way from the previous tick to the
+/// current one.
+pub fn draw_commands(asteroids: &Asteroids, alpha: f32) -> Vec<DrawCommand> {
+    let mut commands = Vec::new();
+
+    // Draw entities
+    for entity in &asteroids.entities {
+        if asteroids.state.jump_times.contains_key(&entity.id) {
+            continue;
+        }
         let mut rotation_z = Matrix4::one();
-        let theta = *asteroids.state.directions.get(&entity.id).unwrap();
+        let theta = interpolated_direction(&asteroids.state, entity.id, alpha);
         rotation_z[0][0] = cgmath::cos(cgmath::deg(theta));
         rotation_z[0][1] = cgmath::sin(cgmath::deg(theta));
         rotation_z[1][0] = -cgmath::sin(cgmath::deg(theta));
         rotation_z[1][1] = cgmath::cos(cgmath::deg(theta));
-        model = model.mul_m(&rotation_z);
 
         let scale = *asteroids.state.scales.get(&entity.id).unwrap();
         let scaling = Matrix4::from_diagonal(scale);
-        model = model.mul_m(&scaling);
 
-        let mvp = asteroids.projection.mul_m(&model);
-        let mvp_array: [f32; 16] = *mvp.as_ref();
+        // Models fit in a unit square, so nothing reaches further than its corners
+        let position = interpolated_position(&asteroids.state, entity.id, alpha);
+        let reach = scale.x.hypot(scale.y) / 2.0;
+        for offset in ghost_offsets(position, reach) {
+            let mut model = Matrix4::one();
+
+            let mut translation = Matrix4::one();
+            translation.replace_col(3, position + offset);
+            model = model.mul_m(&translation);
+            model = model.mul_m(&rotation_z);
+            model = model.mul_m(&scaling);
 
-        unsafe {
-            let (vao, vertices) = *asteroids.state.models.get(&entity.id).unwrap();
-            gl::BindVertexArray(vao);
-            gl::UniformMatrix4fv(1, 1, gl::FALSE, mvp_array.as_ptr());
-            gl::DrawArrays(gl::LINE_LOOP, 0, vertices as i32);
+            commands.push(DrawCommand {
+                model: *asteroids.state.models.get(&entity.id).unwrap(),
+                transform: asteroids.projection.mul_m(&model),
+                colour: render::WHITE,
+            });
         }
     }
 
@@ -254,14 +621,26 @@ pub fn update_and_render(asteroids: &mut Asteroids, input: &HashMap<char, u32>,
         let scaling = Matrix4::from_diagonal(scale);
         model = model.mul_m(&scaling);
 
-        let mvp = asteroids.projection.mul_m(&model);
-        let mvp_array: [f32; 16] = *mvp.as_ref();
-
-        unsafe {
-            let (vao, vertices) = *asteroids.state.models.get(&asteroids.entities[0].id).unwrap();
-            gl::BindVertexArray(vao);
-            gl::UniformMatrix4fv(1, 1, gl::FALSE, mvp_array.as_ptr());
-            gl::DrawArrays(gl::LINE_LOOP, 0, vertices as i32);
-        }
+        commands.push(DrawCommand {
+            model: Model::PlayerShip,
+            transform: asteroids.projection.mul_m(&model),
+            colour: render::WHITE,
+        });
     }
+
+    // Score in the top right corner
+    let score = asteroids.score.to_string();
+    let size = 20.0;
+    commands.extend(font::text(&asteroids.projection,
+                               &score,
+                               780.0 - size * score.len() as f32,
+                               10.0,
+                               size,
+                               render::WHITE));
+
+    commands
+}
+
+pub fn render<R: Renderer>(asteroids: &Asteroids, alpha: f32, renderer: &mut R) {
+    renderer.draw(&draw_commands(asteroids, alpha));
 }
@@ -0,0 +1,179 @@
+use std::collections::BTreeMap;
+use std::collections::btree_map;
+use std::fmt;
+use std::fs::File;
+use std::io::Read;
+use std::io::Write;
+use std::path::Path;
+use super::input::Action;
+use super::input::ACTIONS;
+
+const HEADER: &'static str = "# Each line binds an action to any number of keys and controller buttons, separated by
+# commas. Keys use SDL scancode names, buttons use SDL game controller button names.
+";
+
+/// The bindings used when there is no bindings file.
+pub const DEFAULT: &'static str = "thrust = key W, key Up, button dpup
+rotate_left = key A, key Left, button dpleft
+rotate_right = key D, key Right, button dpright
+fire = key Space, button a
+hyperspace = key Left Shift, button y
+pause = key P, button start
+quit = key Q, key Escape
+";
+
+/// Keys the windowed frontend handles itself, which can not be bound to actions.
+pub const RESERVED_KEYS: [&'static str; 4] = ["f1", "f5", "f9", "f12"];
+
+/// A key or controller button, named the way SDL names it.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Binding {
+    Key(String),
+    Button(String),
+}
+
+impl Binding {
+    // SDL ignores case when looking up names, so they are kept in lowercase to make two
+    // spellings of the same key conflict
+    pub fn key(name: &str) -> Binding {
+        Binding::Key(name.trim().to_lowercase())
+    }
+
+    pub fn button(name: &str) -> Binding {
+        Binding::Button(name.trim().to_lowercase())
+    }
+
+    fn parse(text: &str) -> Result<Binding, String> {
+        let text = text.trim();
+        let (kind, name) = match text.find(char::is_whitespace) {
+            Some(i) => (&text[..i], text[i..].trim()),
+            None => (text, ""),
+        };
+        if name.is_empty() {
+            return Err(format!("Missing name in binding '{}'.", text));
+        }
+        match kind {
+            "key" => Ok(Binding::key(name)),
+            "button" => Ok(Binding::button(name)),
+            _ => Err(format!("Unknown binding '{}', expected 'key NAME' or 'button NAME'.", text)),
+        }
+    }
+}
+
+impl fmt::Display for Binding {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Binding::Key(ref name) => write!(f, "key {}", name),
+            Binding::Button(ref name) => write!(f, "button {}", name),
+        }
+    }
+}
+
+/// Which action each key and controller button triggers. An action can have any number of
+/// bindings, but a binding only ever triggers one action.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Bindings {
+    actions: BTreeMap<Binding, Action>,
+}
+
+impl Bindings {
+    pub fn new() -> Bindings {
+        Bindings { actions: BTreeMap::new() }
+    }
+
+    pub fn defaults() -> Bindings {
+        Bindings::read(DEFAULT).expect("Could not read the default bindings.")
+    }
+
+    /// Binds `binding` to `action`, unless it is reserved or already bound to a different one.
+    pub fn bind(&mut self, binding: Binding, action: Action) -> Result<(), String> {
+        if let Binding::Key(ref name) = binding {
+            if RESERVED_KEYS.contains(&&name[..]) {
+                return Err(format!("'{}' is reserved and can not be bound.", binding));
+            }
+        }
+        match self.actions.get(&binding) {
+            Some(&bound) if bound != action => {
+                return Err(format!("'{}' is bound to both {} and {}.",
+                                   binding,
+                                   bound.name(),
+                                   action.name()));
+            }
+            _ => (),
+        }
+        self.actions.insert(binding, action);
+        Ok(())
+    }
+
+    /// Removes every binding of `action`.
+    pub fn unbind(&mut self, action: Action) {
+        let bindings = self.bindings(action).into_iter().cloned().collect::<Vec<_>>();
+        for binding in bindings {
+            self.actions.remove(&binding);
+        }
+    }
+
+    pub fn action(&self, binding: &Binding) -> Option<Action> {
+        self.actions.get(binding).cloned()
+    }
+
+    pub fn bindings(&self, action: Action) -> Vec<&Binding> {
+        self.actions
+            .iter()
+            .filter(|&(_, &bound)| bound == action)
+            .map(|(binding, _)| binding)
+            .collect()
+    }
+
+    pub fn iter(&self) -> btree_map::Iter<'_, Binding, Action> {
+        self.actions.iter()
+    }
+
+    pub fn write(&self) -> String {
+        let mut out = HEADER.to_string();
+        for action in &ACTIONS {
+            let bindings = self.bindings(*action)
+                               .iter()
+                               .map(|binding| binding.to_string())
+                               .collect::<Vec<_>>();
+            let line = format!("{} = {}", action.name(), bindings.join(", "));
+            out.push_str(line.trim_end());
+            out.push('\n');
+        }
+        out
+    }
+
+    pub fn read(text: &str) -> Result<Bindings, String> {
+        let mut bindings = Bindings::new();
+        for (number, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let error = |e: String| format!("Line {}: {}", number + 1, e);
+            let (name, list) = match line.find('=') {
+                Some(i) => (line[..i].trim(), &line[i + 1..]),
+                None => return Err(error(format!("Expected 'action = bindings', found '{}'.", line))),
+            };
+            let action = Action::from_name(name)
+                             .ok_or(error(format!("Unknown action '{}'.", name)))?;
+            for binding in list.split(',').filter(|b| !b.trim().is_empty()) {
+                let binding = Binding::parse(binding).map_err(&error)?;
+                bindings.bind(binding, action).map_err(&error)?;
+            }
+        }
+        Ok(bindings)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        let mut file = File::create(path).map_err(|e| e.to_string())?;
+        file.write_all(self.write().as_bytes()).map_err(|e| e.to_string())
+    }
+
+    pub fn load(path: &Path) -> Result<Bindings, String> {
+        let mut file = File::open(path).map_err(|e| e.to_string())?;
+        let mut text = String::new();
+        file.read_to_string(&mut text).map_err(|e| e.to_string())?;
+        Bindings::read(&text)
+    }
+}
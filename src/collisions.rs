@@ -1,30 +1,289 @@
-use cgmath::Array;
+use cgmath::Vector4;
+use std::f32;
 use super::entity::Entity;
 use super::entity::EntityState;
 use super::entity::Kind;
+use super::physics;
+use super::spatial_hash::SpatialHash;
 
-pub fn find_collisions(state: &EntityState,
-                       entities: &[Entity])
-                       -> Vec<((u32, Kind), (u32, Kind))> {
-    let mut collisions: Vec<((u32, Kind), (u32, Kind))> = Vec::new();
+// Cells smaller than this cost more to fill than they save
+const MIN_CELL_SIZE: f32 = 10.0;
+
+/// Two entities whose outlines overlap, reported once per pair in the order they appear in
+/// the entity list.
+#[derive(Debug, Clone, Copy)]
+pub struct Collision {
+    pub a: u32,
+    pub kind_a: Kind,
+    pub b: u32,
+    pub kind_b: Kind,
+    /// The middle of the overlap along the line between their centres, in world coordinates.
+    pub contact: Vector4<f32>,
+    /// How far the two overlap along the line between their centres. For entities that passed
+    /// through each other during the tick, how far they moved on after first touching.
+    pub depth: f32,
+}
+
+/// An entity ready for testing, with the outline of its model placed in the world.
+struct Collidable {
+    id: u32,
+    position: Vector4<f32>,
+    /// Reaches the vertex furthest from the position.
+    radius: f32,
+    outline: Vec<(f32, f32)>,
+    /// How far it moved this tick, nothing when it has only just appeared.
+    motion: (f32, f32),
+}
+
+impl Collidable {
+    fn new(state: &EntityState, id: u32) -> Collidable {
+        let position = *state.positions.get(&id).unwrap();
+        let scale = state.scales.get(&id).unwrap();
+        let (sin, cos) = state.directions.get(&id).unwrap().to_radians().sin_cos();
+        // Scaled, rotated and moved the same way as for drawing
+        let outline = state.models
+                           .get(&id)
+                           .unwrap()
+                           .vertices()
+                           .chunks(4)
+                           .map(|v| {
+                               let (x, y) = (v[0] * scale.x, v[1] * scale.y);
+                               (position.x + cos * x - sin * y, position.y + sin * x + cos * y)
+                           })
+                           .collect::<Vec<_>>();
+        let radius = outline.iter()
+                            .map(|&(x, y)| (x - position.x).hypot(y - position.y))
+                            .fold(0.0, f32::max);
+        let motion = match state.previous_positions.get(&id) {
+            Some(previous) => {
+                // The short way round, for entities that wrapped around an edge
+                let d = position - *previous;
+                (physics::wrap_offset(d.x, 800.0), physics::wrap_offset(d.y, 600.0))
+            }
+            None => (0.0, 0.0),
+        };
+        Collidable {
+            id: id,
+            position: position,
+            radius: radius,
+            outline: outline,
+            motion: motion,
+        }
+    }
+
+    /// The middle of the way it moved this tick.
+    fn middle(&self) -> (f32, f32) {
+        (self.position.x - self.motion.0 / 2.0, self.position.y - self.motion.1 / 2.0)
+    }
+
+    /// Reaches everywhere its outline went this tick from the middle.
+    fn reach(&self) -> f32 {
+        self.radius + self.motion.0.hypot(self.motion.1) / 2.0
+    }
+
+    /// A copy moved by (`dx`, `dy`).
+    fn moved(&self, dx: f32, dy: f32) -> Collidable {
+        Collidable {
+            id: self.id,
+            position: self.position + Vector4::new(dx, dy, 0.0, 0.0),
+            radius: self.radius,
+            outline: self.outline.iter().map(|&(x, y)| (x + dx, y + dy)).collect(),
+            motion: self.motion,
+        }
+    }
+}
+
+pub fn find_collisions(state: &EntityState, entities: &[Entity]) -> Vec<Collision> {
+    let mut collisions = Vec::new();
+    // Entities in hyperspace are out of reach
     let collidables = entities.iter()
-                              .map(|e| {
-                                  (e.id,
-                                   *state.positions.get(&e.id).unwrap(),
-                                   state.scales.get(&e.id).unwrap().max() / 2.0)
-                              })
+                              .filter(|e| !state.jump_times.contains_key(&e.id))
+                              .map(|e| Collidable::new(state, e.id))
                               .collect::<Vec<_>>();
 
-    for &(a, a_position, a_radius) in &collidables {
-        for &(b, b_position, b_radius) in &collidables {
-            let d = b_position - a_position;
-            let distance = d.x.powf(2.0) + d.y.powf(2.0);
-            if distance < (a_radius + b_radius).powf(2.0) {
-                let kind_a = state.kinds.get(&a).unwrap();
-                let kind_b = state.kinds.get(&b).unwrap();
-                collisions.push(((a, (*kind_a).clone()), (b, (*kind_b).clone())));
+    // Entities that touch are at most the widest entity apart, so with cells that wide they
+    // are always in neighbouring cells. Moving entities are as wide as all the way they moved.
+    let cell_size = collidables.iter().fold(MIN_CELL_SIZE, |size, c| size.max(c.reach() * 2.0));
+    let mut grid = SpatialHash::new(800.0, 600.0, cell_size);
+    for (index, collidable) in collidables.iter().enumerate() {
+        let (x, y) = collidable.middle();
+        grid.insert(index, x, y);
+    }
+
+    let mut hits = grid.pairs()
+                       .into_iter()
+                       .filter_map(|(i, j)| {
+                           overlap(&collidables[i], &collidables[j])
+                               .map(|(contact, depth)| (i, j, contact, depth))
+                       })
+                       .collect::<Vec<_>>();
+    // Keep to the order of the entity list, so the game plays out the same however the grid
+    // is laid out
+    hits.sort_by_key(|&(i, j, _, _)| (i, j));
+
+    for (i, j, contact, depth) in hits {
+        let (a, b) = (&collidables[i], &collidables[j]);
+        collisions.push(Collision {
+            a: a.id,
+            kind_a: *state.kinds.get(&a.id).unwrap(),
+            b: b.id,
+            kind_b: *state.kinds.get(&b.id).unwrap(),
+            // Entities brought round to meet across an edge may touch outside the world
+            contact: Vector4::new(contact.0.rem_euclid(800.0),
+                                  contact.1.rem_euclid(600.0),
+                                  0.0,
+                                  1.0),
+            depth: depth,
+        });
+    }
+    collisions
+}
+
+/// Where and how deep two entities overlap, if they do now or passed through each other on
+/// the way here.
+fn overlap(a: &Collidable, b: &Collidable) -> Option<((f32, f32), f32)> {
+    // Outlines can only meet if the circles around everywhere they went do, measured the short
+    // way round the world
+    let ((ax, ay), (bx, by)) = (a.middle(), b.middle());
+    let (dx, dy) = (physics::wrap_offset(bx - ax, 800.0), physics::wrap_offset(by - ay, 600.0));
+    if dx * dx + dy * dy >= (a.reach() + b.reach()) * (a.reach() + b.reach()) {
+        return None;
+    }
+    // Bring `b` round to the side of `a` it is closest on
+    let moved;
+    let b = if dx != bx - ax || dy != by - ay {
+        moved = b.moved(dx - (bx - ax), dy - (by - ay));
+        &moved
+    } else {
+        b
+    };
+    if outlines_overlap(&a.outline, &b.outline) {
+        return Some(resting_overlap(a, b));
+    }
+    let motion = (a.motion.0 - b.motion.0, a.motion.1 - b.motion.1);
+    if motion == (0.0, 0.0) {
+        return None;
+    }
+    first_touch(&a.outline, &b.outline, motion).map(|(t, (x, y))| {
+        // Back to where `b` was at the time
+        let back = 1.0 - t;
+        ((x - b.motion.0 * back, y - b.motion.1 * back), back * motion.0.hypot(motion.1))
+    })
+}
+
+/// The middle and depth of the overlap of two outlines that overlap where they are.
+fn resting_overlap(a: &Collidable, b: &Collidable) -> ((f32, f32), f32) {
+    let d = b.position - a.position;
+    let distance = (d.x * d.x + d.y * d.y).sqrt();
+    // Entities on top of each other overlap the same in every direction
+    let (nx, ny) = if distance > 0.0 {
+        (d.x / distance, d.y / distance)
+    } else {
+        (1.0, 0.0)
+    };
+    let (a_start, a_end) = extent(&a.outline, nx, ny);
+    let (b_start, b_end) = extent(&b.outline, nx, ny);
+    let (start, end) = (a_start.max(b_start), a_end.min(b_end));
+    let along = (start + end) / 2.0 - (a.position.x * nx + a.position.y * ny);
+    ((a.position.x + nx * along, a.position.y + ny * along), end - start)
+}
+
+/// When and where outline `a`, having moved by `motion` relative to `b`, first touched it
+/// along the way, as the fraction of the way it had moved and where they touched with `b` left
+/// where it ended up.
+/// Either a vertex of `a` ran into an edge of `b`, or a vertex of `b` into an edge of `a`.
+fn first_touch(a: &[(f32, f32)],
+               b: &[(f32, f32)],
+               motion: (f32, f32))
+               -> Option<(f32, (f32, f32))> {
+    let mut first: Option<(f32, (f32, f32))> = None;
+    for &p in a {
+        let from = (p.0 - motion.0, p.1 - motion.1);
+        for j in 0..b.len() {
+            let (r, s) = edge(b, j);
+            if let Some(t) = crossing(from, p, r, s) {
+                if first.map_or(true, |(earliest, _)| t < earliest) {
+                    let back = 1.0 - t;
+                    first = Some((t, (p.0 - motion.0 * back, p.1 - motion.1 * back)));
+                }
             }
         }
     }
-    collisions
+    for &q in b {
+        let from = (q.0 + motion.0, q.1 + motion.1);
+        for i in 0..a.len() {
+            let (r, s) = edge(a, i);
+            if let Some(t) = crossing(from, q, r, s) {
+                if first.map_or(true, |(earliest, _)| t < earliest) {
+                    first = Some((t, q));
+                }
+            }
+        }
+    }
+    first
+}
+
+/// Whether two closed outlines overlap, either with their edges crossing or with one inside
+/// the other.
+fn outlines_overlap(a: &[(f32, f32)], b: &[(f32, f32)]) -> bool {
+    for i in 0..a.len() {
+        let (p, q) = edge(a, i);
+        for j in 0..b.len() {
+            let (r, s) = edge(b, j);
+            if segments_cross(p, q, r, s) {
+                return true;
+            }
+        }
+    }
+    // Without crossing edges, one is either wholly inside the other or they are apart
+    contains(a, b[0]) || contains(b, a[0])
+}
+
+// The edge from vertex `i` to the next one, closing the loop after the last
+fn edge(outline: &[(f32, f32)], i: usize) -> ((f32, f32), (f32, f32)) {
+    (outline[i], outline[(i + 1) % outline.len()])
+}
+
+// Positive when `p` is to the left of the line from `a` to `b`, negative to the right
+fn side(a: (f32, f32), b: (f32, f32), p: (f32, f32)) -> f32 {
+    (b.0 - a.0) * (p.1 - a.1) - (b.1 - a.1) * (p.0 - a.0)
+}
+
+fn segments_cross(p: (f32, f32), q: (f32, f32), r: (f32, f32), s: (f32, f32)) -> bool {
+    (side(r, s, p) > 0.0) != (side(r, s, q) > 0.0) &&
+    (side(p, q, r) > 0.0) != (side(p, q, s) > 0.0)
+}
+
+// How far along from `p` to `q` the segment crosses the one from `r` to `s`, if it does
+fn crossing(p: (f32, f32), q: (f32, f32), r: (f32, f32), s: (f32, f32)) -> Option<f32> {
+    if segments_cross(p, q, r, s) {
+        let (before, after) = (side(r, s, p), side(r, s, q));
+        Some(before / (before - after))
+    } else {
+        None
+    }
+}
+
+/// Whether `point` is inside `outline`, counting the edges a ray from it crosses. Edges drawn
+/// there and back cross twice and so do not count.
+fn contains(outline: &[(f32, f32)], point: (f32, f32)) -> bool {
+    let mut inside = false;
+    for i in 0..outline.len() {
+        let ((ax, ay), (bx, by)) = edge(outline, i);
+        if (ay > point.1) != (by > point.1) {
+            let x = ax + (point.1 - ay) / (by - ay) * (bx - ax);
+            if x > point.0 {
+                inside = !inside;
+            }
+        }
+    }
+    inside
+}
+
+// The range an outline covers along the direction (`nx`, `ny`)
+fn extent(outline: &[(f32, f32)], nx: f32, ny: f32) -> (f32, f32) {
+    outline.iter()
+           .map(|&(x, y)| x * nx + y * ny)
+           .fold((f32::INFINITY, f32::NEG_INFINITY),
+                 |(start, end), along| (start.min(along), end.max(along)))
 }
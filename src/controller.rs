@@ -0,0 +1,106 @@
+use super::bindings::Binding;
+use super::bindings::Bindings;
+use super::input::Action;
+use super::input::Input;
+use super::input::Source;
+use super::input::Sources;
+
+// Sticks and triggers have to be pushed past PRESS to press an action and come back under
+// RELEASE to let go of it, so a value hovering around one threshold doesn't flicker
+const PRESS: i16 = 16384;
+const RELEASE: i16 = 8192;
+
+/// An analog axis of a game controller, named after SDL2's.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Axis {
+    LeftX,
+    LeftY,
+    RightX,
+    RightY,
+    TriggerLeft,
+    TriggerRight,
+}
+
+impl Axis {
+    /// The actions pushing the axis towards its negative and positive ends press.
+    fn actions(&self) -> (Option<Action>, Option<Action>) {
+        match *self {
+            Axis::LeftX => (Some(Action::RotateLeft), Some(Action::RotateRight)),
+            Axis::TriggerRight => (None, Some(Action::Thrust)),
+            _ => (None, None),
+        }
+    }
+}
+
+/// Something that happened on a game controller, which is identified by SDL2's instance id.
+///
+/// The windowed frontend makes these from SDL2 events, tests can make them up.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ControllerEvent {
+    Axis {
+        which: i32,
+        axis: Axis,
+        value: i16,
+    },
+    Button {
+        which: i32,
+        button: String,
+        pressed: bool,
+    },
+    Removed {
+        which: i32,
+    },
+}
+
+/// A button, or one end of a stick or trigger, of a game controller.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Control {
+    Button(String),
+    Negative(Axis),
+    Positive(Axis),
+}
+
+/// Turns a game controller event into presses and releases of actions, so that sticks and
+/// triggers steer the ship exactly like keys do.
+pub fn handle(event: ControllerEvent,
+              bindings: &Bindings,
+              sources: &mut Sources,
+              input: &mut Input) {
+    match event {
+        ControllerEvent::Axis { which, axis, value } => {
+            let (negative, positive) = axis.actions();
+            if let Some(action) = negative {
+                let source = Source::Controller(which, Control::Negative(axis));
+                let held = sources.holds(&source);
+                let down = value < if held { -RELEASE } else { -PRESS };
+                if down != held {
+                    sources.set(source, action, down, input);
+                }
+            }
+            if let Some(action) = positive {
+                let source = Source::Controller(which, Control::Positive(axis));
+                let held = sources.holds(&source);
+                let down = value > if held { RELEASE } else { PRESS };
+                if down != held {
+                    sources.set(source, action, down, input);
+                }
+            }
+        }
+        ControllerEvent::Button { which, button, pressed } => {
+            if let Some(action) = bindings.action(&Binding::button(&button)) {
+                sources.set(Source::Controller(which, Control::Button(button)),
+                            action,
+                            pressed,
+                            input);
+            }
+        }
+        ControllerEvent::Removed { which } => {
+            // Whatever an unplugged controller was holding is let go
+            sources.release_all(|source| match *source {
+                                    Source::Controller(id, _) => id == which,
+                                    _ => false,
+                                },
+                                input);
+        }
+    }
+}
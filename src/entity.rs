@@ -4,7 +4,16 @@ extern crate rand;
 use cgmath::Vector4;
 use cgmath::Vector;
 use self::rand::Rng;
+use self::rand::SeedableRng;
+use self::rand::isaac::Isaac64Rng;
 use std::collections::HashMap;
+use std::fmt::Write;
+use super::models::Model;
+use super::physics;
+use super::physics::Handling;
+use super::physics::Integrator;
+use super::snapshot::Record;
+use super::tunables::Tunables;
 
 #[derive(Debug, Clone, Copy)]
 pub enum Size {
@@ -13,13 +22,23 @@ pub enum Size {
     Small,
 }
 
+/// Large saucers fire at random, small ones aim at the ship.
+#[derive(Debug, Clone, Copy)]
+pub enum SaucerSize {
+    Large,
+    Small,
+}
+
 #[derive(Debug, Clone, Copy)]
 pub enum Kind {
     PlayerShip,
     ProjectileFriendly,
+    ProjectileHostile,
     Asteroid(Size),
+    Saucer(SaucerSize),
 }
 
+#[derive(Debug, Clone, Copy)]
 pub struct Entity {
     pub id: u32,
 }
@@ -29,49 +48,57 @@ impl Entity {
         Entity { id: id }
     }
 
-    pub fn player_ship(state: &mut EntityState) -> Entity {
+    pub fn player_ship(state: &mut EntityState, handling: Handling) -> Entity {
         let entity = Entity::new(state.next_id());
         state.add_kind(entity.id, Kind::PlayerShip);
         state.add_acceleration(entity.id, Vector4::zero());
         state.add_position(entity.id, Vector4::new(400.0, 300.0, 0.0, 1.0));
         state.add_velocity(entity.id, Vector4::zero());
+        state.add_drag(entity.id, handling.drag());
+        state.add_max_speed(entity.id, handling.max_speed());
         state.add_direction(entity.id, 0.0);
-        state.add_model(entity.id, (1, 3));
+        state.add_angular_velocity(entity.id, 0.0);
+        state.add_model(entity.id, Model::PlayerShip);
         state.add_scale(entity.id, Vector4::new(20.0, 30.0, 0.0, 1.0));
         state.add_weapon_cooldown(entity.id, 0.0);
+        state.add_hyperspace_cooldown(entity.id, 0.0);
         entity
     }
 
-    fn asteroid(state: &mut EntityState, size: Size, position: Option<Vector4<f32>>) -> Entity {
+    fn asteroid(state: &mut EntityState,
+                tunables: &Tunables,
+                size: Size,
+                position: Option<Vector4<f32>>)
+                -> Entity {
         let entity = Entity::new(state.next_id());
         state.add_kind(entity.id, Kind::Asteroid(size));
 
-        let mut rng = rand::StdRng::new().expect("Could not load random number generator.");
-
         let p = match position {
             Some(position) => position,
             None => {
-                if rng.next_u32() % 2 == 0 {
-                    let px = rng.next_f32() * 800.0;
+                if state.rng.next_u32() % 2 == 0 {
+                    let px = state.rng.next_f32() * 800.0;
                     Vector4::new(px, 0.0, 0.0, 1.0)
                 } else {
-                    let py = rng.next_f32() * 600.0;
+                    let py = state.rng.next_f32() * 600.0;
                     Vector4::new(0.0, py, 0.0, 1.0)
                 }
             }
         };
         state.add_position(entity.id, p);
 
-        let dir = rng.next_f32() * 360.0;
+        let dir = state.rng.next_f32() * 360.0;
         state.add_direction(entity.id, dir);
 
         let mut acceleration: Vector4<f32> = Vector4::zero();
         acceleration.x += cgmath::sin(cgmath::deg(dir));
         acceleration.y += -cgmath::cos(cgmath::deg(dir));
-        acceleration = acceleration * (100.0 + rng.next_f32() * 100.0);
+        let speed_range = tunables.asteroid_max_speed - tunables.asteroid_min_speed;
+        acceleration = acceleration * (tunables.asteroid_min_speed +
+                                       state.rng.next_f32() * speed_range);
 
         state.add_velocity(entity.id, acceleration);
-        state.add_model(entity.id, (3, 10));
+        state.add_model(entity.id, Model::Asteroid);
 
         let s = match size {
             Size::Large => Vector4::new(50.0, 50.0, 0.0, 1.0),
@@ -80,52 +107,195 @@ impl Entity {
         };
         state.add_scale(entity.id, s);
 
+        // Tumble up to a quarter turn a second either way
+        let spin = state.rng.next_f32() * 180.0 - 90.0;
+        state.add_angular_velocity(entity.id, spin);
+
         entity
     }
 
-    pub fn large_asteroid(state: &mut EntityState) -> Entity {
-        Entity::asteroid(state, Size::Large, None)
+    pub fn large_asteroid(state: &mut EntityState, tunables: &Tunables) -> Entity {
+        Entity::asteroid(state, tunables, Size::Large, None)
     }
 
-    pub fn medium_asteroid(state: &mut EntityState, position: Vector4<f32>) -> Entity {
-        Entity::asteroid(state, Size::Medium, Some(position))
+    pub fn medium_asteroid(state: &mut EntityState,
+                           tunables: &Tunables,
+                           position: Vector4<f32>)
+                           -> Entity {
+        Entity::asteroid(state, tunables, Size::Medium, Some(position))
     }
 
-    pub fn small_asteroid(state: &mut EntityState, position: Vector4<f32>) -> Entity {
-        Entity::asteroid(state, Size::Small, Some(position))
+    pub fn small_asteroid(state: &mut EntityState,
+                          tunables: &Tunables,
+                          position: Vector4<f32>)
+                          -> Entity {
+        Entity::asteroid(state, tunables, Size::Small, Some(position))
     }
 
-    pub fn projectile(state: &mut EntityState, pos: Vector4<f32>, dir: f32) -> Entity {
+    fn shot(state: &mut EntityState,
+            tunables: &Tunables,
+            kind: Kind,
+            pos: Vector4<f32>,
+            dir: f32)
+            -> Entity {
         let entity = Entity::new(state.next_id());
-        state.add_kind(entity.id, Kind::ProjectileFriendly);
+        state.add_kind(entity.id, kind);
 
         state.add_position(entity.id, pos);
+        // Fired this tick, so it moves on from here and is swept from here too
+        state.previous_positions.insert(entity.id, pos);
         state.add_direction(entity.id, dir);
 
         let mut acceleration: Vector4<f32> = Vector4::zero();
         acceleration.x += cgmath::sin(cgmath::deg(dir));
         acceleration.y += -cgmath::cos(cgmath::deg(dir));
-        acceleration = acceleration * 500.0;
+        acceleration = acceleration * tunables.projectile_speed;
 
         state.add_velocity(entity.id, acceleration);
-        state.add_model(entity.id, (2, 4));
+        state.add_model(entity.id, Model::Projectile);
         state.add_scale(entity.id, Vector4::new(5.0, 5.0, 0.0, 1.0));
-        state.add_lifetime(entity.id, 0.75);
+        state.add_lifetime(entity.id, tunables.projectile_lifetime);
 
         entity
     }
 
-    pub fn update(&self, state: &mut EntityState, t: f32) {
-        let mut zero = Vector4::zero();
-        let acceleration = match state.accelerations.get_mut(&self.id) {
-            Some(i) => i,
-            None => &mut zero,
+    pub fn projectile(state: &mut EntityState,
+                      tunables: &Tunables,
+                      pos: Vector4<f32>,
+                      dir: f32)
+                      -> Entity {
+        Entity::shot(state, tunables, Kind::ProjectileFriendly, pos, dir)
+    }
+
+    /// A saucer flying in from the left or right edge, small with a chance of `small_chance`.
+    pub fn saucer(state: &mut EntityState, tunables: &Tunables, small_chance: f32) -> Entity {
+        let entity = Entity::new(state.next_id());
+        let size = if state.rng.next_f32() < small_chance {
+            SaucerSize::Small
+        } else {
+            SaucerSize::Large
+        };
+        state.add_kind(entity.id, Kind::Saucer(size));
+
+        let y = state.rng.next_f32() * 600.0;
+        let (x, speed) = if state.rng.next_u32() % 2 == 0 {
+            (0.0, tunables.saucer_speed)
+        } else {
+            (800.0, -tunables.saucer_speed)
+        };
+        state.add_position(entity.id, Vector4::new(x, y, 0.0, 1.0));
+        state.add_velocity(entity.id, Vector4::new(speed, 0.0, 0.0, 0.0));
+        state.add_direction(entity.id, 0.0);
+        state.add_model(entity.id, Model::Saucer);
+
+        let s = match size {
+            SaucerSize::Large => Vector4::new(50.0, 50.0, 0.0, 1.0),
+            SaucerSize::Small => Vector4::new(25.0, 25.0, 0.0, 1.0),
+        };
+        state.add_scale(entity.id, s);
+        state.add_weapon_cooldown(entity.id, tunables.saucer_fire_interval);
+
+        entity
+    }
+
+    /// Steers a saucer along its zig-zag across the screen: level, diagonally down, level,
+    /// diagonally up and level again, a fifth of the way each.
+    pub fn zigzag(&self, state: &mut EntityState, tunables: &Tunables) {
+        let position = state.positions.get(&self.id).unwrap();
+        let velocity = state.velocities.get_mut(&self.id).unwrap();
+        let (speed, travelled) = if velocity.x > 0.0 {
+            (tunables.saucer_speed, position.x)
+        } else {
+            (-tunables.saucer_speed, 800.0 - position.x)
         };
-        *acceleration = *acceleration * 1000.0;
+        velocity.x = speed;
+        velocity.y = match (travelled / 160.0) as u32 {
+            1 => tunables.saucer_speed,
+            3 => -tunables.saucer_speed,
+            _ => 0.0,
+        };
+    }
+
+    /// Whether a saucer has flown the width of the screen. Going out over the far edge wraps
+    /// it round to the edge it came in over.
+    pub fn has_crossed(&self, state: &EntityState) -> bool {
+        let position = state.positions.get(&self.id).unwrap();
+        let rightwards = state.velocities.get(&self.id).unwrap().x > 0.0;
+        let travelled = if rightwards {
+            position.x
+        } else {
+            800.0 - position.x
+        };
+        let wrapped = state.previous_positions.get(&self.id).map_or(false, |previous| {
+            if rightwards {
+                position.x < previous.x
+            } else {
+                position.x > previous.x
+            }
+        });
+        wrapped || travelled >= 800.0
+    }
+
+    /// Fires a hostile shot from a saucer. A large saucer fires anywhere, a small one at
+    /// `target`, missing by up to `spread` degrees either way.
+    pub fn saucer_shot(&self,
+                       state: &mut EntityState,
+                       tunables: &Tunables,
+                       target: Vector4<f32>,
+                       spread: f32)
+                       -> Entity {
+        let position = *state.positions.get(&self.id).unwrap();
+        let dir = match *state.kinds.get(&self.id).unwrap() {
+            Kind::Saucer(SaucerSize::Large) => state.rng.next_f32() * 360.0,
+            _ => {
+                let aim = (target.x - position.x).atan2(position.y - target.y).to_degrees();
+                aim + (state.rng.next_f32() * 2.0 - 1.0) * spread
+            }
+        };
+        state.weapon_cooldowns.insert(self.id, tunables.saucer_fire_interval);
+        Entity::shot(state, tunables, Kind::ProjectileHostile, position, dir)
+    }
+
+    /// Sends the entity into hyperspace, where it stands still until it re-enters.
+    pub fn jump(&self, state: &mut EntityState, tunables: &Tunables) {
+        state.velocities.insert(self.id, Vector4::zero());
+        state.jump_times.insert(self.id, tunables.hyperspace_vanish_time);
+        state.hyperspace_cooldowns.insert(self.id, tunables.hyperspace_cooldown);
+    }
+
+    /// Brings the entity back out of hyperspace at a random position, and returns whether
+    /// it broke up on the way.
+    pub fn reenter(&self, state: &mut EntityState, tunables: &Tunables) -> bool {
+        state.jump_times.remove(&self.id);
+        let position = Vector4::new(state.rng.next_f32() * 800.0,
+                                    state.rng.next_f32() * 600.0,
+                                    0.0,
+                                    1.0);
+        state.positions.insert(self.id, position);
+        // Appear at the new position rather than sliding there from the old one
+        state.previous_positions.insert(self.id, position);
+        state.rng.next_f32() < tunables.hyperspace_destruction_chance
+    }
+
+    pub fn update(&self, state: &mut EntityState, t: f32) {
+        let acceleration = state.accelerations.get(&self.id).cloned().unwrap_or(Vector4::zero());
+        let drag = state.drags.get(&self.id).cloned().unwrap_or(0.0);
         let position = state.positions.get_mut(&self.id).unwrap();
         let velocity = state.velocities.get_mut(&self.id).unwrap();
-        *position = *acceleration * t * t * 0.5f32 + *velocity * t + *position;
-        *velocity = *acceleration * t + *velocity;
+        state.integrator.integrate(position, velocity, acceleration, drag, t);
+        if let Some(&max_speed) = state.max_speeds.get(&self.id) {
+            physics::cap_speed(velocity, max_speed);
+        }
+
+        if let Some(angular_velocity) = state.angular_velocities.get_mut(&self.id) {
+            let angular_acceleration = state.angular_accelerations
+                                            .get(&self.id)
+                                            .cloned()
+                                            .unwrap_or(0.0);
+            let direction = state.directions.get_mut(&self.id).unwrap();
+            *direction += angular_acceleration * t * t * 0.5 + *angular_velocity * t;
+            *angular_velocity += angular_acceleration * t;
+        }
 
         if position.x < 0.0 {
             position.x = 800.0;
@@ -147,36 +317,108 @@ impl Entity {
             *weapon_cooldown -= t;
         }
 
-        *acceleration = Vector4::zero();
+        if let Some(hyperspace_cooldown) = state.hyperspace_cooldowns.get_mut(&self.id) {
+            *hyperspace_cooldown -= t;
+        }
+
+        if let Some(jump_time) = state.jump_times.get_mut(&self.id) {
+            *jump_time -= t;
+        }
+
+        if let Some(acceleration) = state.accelerations.get_mut(&self.id) {
+            *acceleration = Vector4::zero();
+        }
+    }
+}
+
+/// Isaac64 rather than StdRng so a seed gives the same game on 32 and 64 bit targets.
+/// Counts its draws so that it can be saved and restored as a seed and a draw count.
+pub struct GameRng {
+    seed: u64,
+    draws: u64,
+    rng: Isaac64Rng,
+}
+
+impl GameRng {
+    fn new(seed: u64) -> GameRng {
+        GameRng::restore(seed, 0)
+    }
+
+    fn restore(seed: u64, draws: u64) -> GameRng {
+        let mut rng = GameRng {
+            seed: seed,
+            draws: 0,
+            rng: Isaac64Rng::from_seed(&[seed]),
+        };
+        for _ in 0..draws {
+            rng.next_u64();
+        }
+        rng
+    }
+}
+
+impl Rng for GameRng {
+    fn next_u32(&mut self) -> u32 {
+        self.next_u64() as u32
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.draws += 1;
+        self.rng.next_u64()
     }
 }
 
 pub struct EntityState {
     entity_count: u32,
+    rng: GameRng,
+    pub integrator: Integrator,
     pub kinds: HashMap<u32, Kind>,
     pub accelerations: HashMap<u32, Vector4<f32>>,
     pub positions: HashMap<u32, Vector4<f32>>,
+    pub previous_positions: HashMap<u32, Vector4<f32>>,
     pub velocities: HashMap<u32, Vector4<f32>>,
+    /// Fraction of its velocity an entity loses every second.
+    pub drags: HashMap<u32, f32>,
+    pub max_speeds: HashMap<u32, f32>,
     pub directions: HashMap<u32, f32>,
-    pub models: HashMap<u32, (u32, u32)>,
+    pub previous_directions: HashMap<u32, f32>,
+    /// Degrees per second, positive turning clockwise on screen.
+    pub angular_velocities: HashMap<u32, f32>,
+    /// Degrees per second per second.
+    pub angular_accelerations: HashMap<u32, f32>,
+    pub models: HashMap<u32, Model>,
     pub scales: HashMap<u32, Vector4<f32>>,
     pub lifetimes: HashMap<u32, f32>,
     pub weapon_cooldowns: HashMap<u32, f32>,
+    pub hyperspace_cooldowns: HashMap<u32, f32>,
+    /// Seconds until an entity in hyperspace re-enters. Entities in hyperspace are neither
+    /// drawn nor collided with.
+    pub jump_times: HashMap<u32, f32>,
 }
 
 impl EntityState {
-    pub fn new() -> EntityState {
+    pub fn new(seed: u64) -> EntityState {
         EntityState {
             entity_count: 0,
+            rng: GameRng::new(seed),
+            integrator: Integrator::VelocityVerlet,
             kinds: HashMap::new(),
             accelerations: HashMap::new(),
             positions: HashMap::new(),
+            previous_positions: HashMap::new(),
             velocities: HashMap::new(),
+            drags: HashMap::new(),
+            max_speeds: HashMap::new(),
             directions: HashMap::new(),
+            previous_directions: HashMap::new(),
+            angular_velocities: HashMap::new(),
+            angular_accelerations: HashMap::new(),
             models: HashMap::new(),
             scales: HashMap::new(),
             lifetimes: HashMap::new(),
             weapon_cooldowns: HashMap::new(),
+            hyperspace_cooldowns: HashMap::new(),
+            jump_times: HashMap::new(),
         }
     }
 
@@ -202,11 +444,23 @@ impl EntityState {
         self.directions.insert(id, direction);
     }
 
+    fn add_angular_velocity(&mut self, id: u32, angular_velocity: f32) {
+        self.angular_velocities.insert(id, angular_velocity);
+    }
+
     fn add_velocity(&mut self, id: u32, velocity: Vector4<f32>) {
         self.velocities.insert(id, velocity);
     }
 
-    fn add_model(&mut self, id: u32, model: (u32, u32)) {
+    fn add_drag(&mut self, id: u32, drag: f32) {
+        self.drags.insert(id, drag);
+    }
+
+    fn add_max_speed(&mut self, id: u32, max_speed: f32) {
+        self.max_speeds.insert(id, max_speed);
+    }
+
+    fn add_model(&mut self, id: u32, model: Model) {
         self.models.insert(id, model);
     }
 
@@ -222,15 +476,175 @@ impl EntityState {
         self.weapon_cooldowns.insert(id, weapon_cooldown);
     }
 
+    fn add_hyperspace_cooldown(&mut self, id: u32, hyperspace_cooldown: f32) {
+        self.hyperspace_cooldowns.insert(id, hyperspace_cooldown);
+    }
+
     pub fn remove(&mut self, id: u32) {
         self.kinds.remove(&id);
         self.accelerations.remove(&id);
         self.positions.remove(&id);
+        self.previous_positions.remove(&id);
         self.velocities.remove(&id);
+        self.drags.remove(&id);
+        self.max_speeds.remove(&id);
         self.directions.remove(&id);
+        self.previous_directions.remove(&id);
+        self.angular_velocities.remove(&id);
+        self.angular_accelerations.remove(&id);
         self.models.remove(&id);
         self.scales.remove(&id);
         self.lifetimes.remove(&id);
         self.weapon_cooldowns.remove(&id);
+        self.hyperspace_cooldowns.remove(&id);
+        self.jump_times.remove(&id);
+    }
+
+    pub fn write_snapshot(&self, out: &mut String) {
+        writeln!(out, "rng {} {}", self.rng.seed, self.rng.draws).unwrap();
+        writeln!(out, "entity_count {}", self.entity_count).unwrap();
+        writeln!(out, "integrator {}", self.integrator.name()).unwrap();
+        for (id, kind) in sorted(&self.kinds) {
+            let kind = match *kind {
+                Kind::PlayerShip => "player_ship",
+                Kind::ProjectileFriendly => "projectile_friendly",
+                Kind::ProjectileHostile => "projectile_hostile",
+                Kind::Asteroid(Size::Large) => "asteroid_large",
+                Kind::Asteroid(Size::Medium) => "asteroid_medium",
+                Kind::Asteroid(Size::Small) => "asteroid_small",
+                Kind::Saucer(SaucerSize::Large) => "saucer_large",
+                Kind::Saucer(SaucerSize::Small) => "saucer_small",
+            };
+            writeln!(out, "kind {} {}", id, kind).unwrap();
+        }
+        write_vectors(out, "acceleration", &self.accelerations);
+        write_vectors(out, "position", &self.positions);
+        write_vectors(out, "previous_position", &self.previous_positions);
+        write_vectors(out, "velocity", &self.velocities);
+        write_scalars(out, "drag", &self.drags);
+        write_scalars(out, "max_speed", &self.max_speeds);
+        write_scalars(out, "direction", &self.directions);
+        write_scalars(out, "previous_direction", &self.previous_directions);
+        write_scalars(out, "angular_velocity", &self.angular_velocities);
+        write_scalars(out, "angular_acceleration", &self.angular_accelerations);
+        for (id, model) in sorted(&self.models) {
+            let model = match *model {
+                Model::PlayerShip => "player_ship",
+                Model::Projectile => "projectile",
+                Model::Asteroid => "asteroid",
+                Model::Saucer => "saucer",
+                Model::Glyph(_) => unreachable!("Entities are never drawn as text."),
+            };
+            writeln!(out, "model {} {}", id, model).unwrap();
+        }
+        write_vectors(out, "scale", &self.scales);
+        write_scalars(out, "lifetime", &self.lifetimes);
+        write_scalars(out, "weapon_cooldown", &self.weapon_cooldowns);
+        write_scalars(out, "hyperspace_cooldown", &self.hyperspace_cooldowns);
+        write_scalars(out, "jump_time", &self.jump_times);
+    }
+
+    pub fn read_record(&mut self, record: &Record) -> Result<(), String> {
+        match record.name {
+            "rng" => self.rng = GameRng::restore(record.get(0)?, record.get(1)?),
+            "entity_count" => self.entity_count = record.get(0)?,
+            "integrator" => {
+                self.integrator = Integrator::from_name(record.get_str(0)?)
+                                      .ok_or(record.unknown())?;
+            }
+            "kind" => {
+                let kind = match record.get_str(1)? {
+                    "player_ship" => Kind::PlayerShip,
+                    "projectile_friendly" => Kind::ProjectileFriendly,
+                    "projectile_hostile" => Kind::ProjectileHostile,
+                    "asteroid_large" => Kind::Asteroid(Size::Large),
+                    "asteroid_medium" => Kind::Asteroid(Size::Medium),
+                    "asteroid_small" => Kind::Asteroid(Size::Small),
+                    "saucer_large" => Kind::Saucer(SaucerSize::Large),
+                    "saucer_small" => Kind::Saucer(SaucerSize::Small),
+                    _ => return Err(record.unknown()),
+                };
+                self.kinds.insert(record.get(0)?, kind);
+            }
+            "acceleration" => read_vector(record, &mut self.accelerations)?,
+            "position" => read_vector(record, &mut self.positions)?,
+            "previous_position" => read_vector(record, &mut self.previous_positions)?,
+            "velocity" => read_vector(record, &mut self.velocities)?,
+            "drag" => read_scalar(record, &mut self.drags)?,
+            "max_speed" => read_scalar(record, &mut self.max_speeds)?,
+            "direction" => read_scalar(record, &mut self.directions)?,
+            "previous_direction" => read_scalar(record, &mut self.previous_directions)?,
+            "angular_velocity" => read_scalar(record, &mut self.angular_velocities)?,
+            "angular_acceleration" => read_scalar(record, &mut self.angular_accelerations)?,
+            "model" => {
+                let model = match record.get_str(1)? {
+                    "player_ship" => Model::PlayerShip,
+                    "projectile" => Model::Projectile,
+                    "asteroid" => Model::Asteroid,
+                    "saucer" => Model::Saucer,
+                    _ => return Err(record.unknown()),
+                };
+                self.models.insert(record.get(0)?, model);
+            }
+            "scale" => read_vector(record, &mut self.scales)?,
+            "lifetime" => read_scalar(record, &mut self.lifetimes)?,
+            "weapon_cooldown" => read_scalar(record, &mut self.weapon_cooldowns)?,
+            "hyperspace_cooldown" => read_scalar(record, &mut self.hyperspace_cooldowns)?,
+            "jump_time" => read_scalar(record, &mut self.jump_times)?,
+            _ => return Err(record.unknown()),
+        }
+        Ok(())
+    }
+
+    /// Checks that an entity has every component that updating and drawing it relies on,
+    /// including the ones only its kind needs.
+    pub fn is_complete(&self, id: u32) -> bool {
+        let common = id < self.entity_count && self.positions.contains_key(&id) &&
+                     self.velocities.contains_key(&id) &&
+                     self.directions.contains_key(&id) &&
+                     self.models.contains_key(&id) && self.scales.contains_key(&id);
+        common &&
+        match self.kinds.get(&id) {
+            Some(&Kind::PlayerShip) => {
+                self.accelerations.contains_key(&id) &&
+                self.angular_velocities.contains_key(&id) &&
+                self.weapon_cooldowns.contains_key(&id) &&
+                self.hyperspace_cooldowns.contains_key(&id)
+            }
+            Some(&Kind::Saucer(_)) => self.weapon_cooldowns.contains_key(&id),
+            Some(_) => true,
+            None => false,
+        }
     }
 }
+
+fn sorted<T>(components: &HashMap<u32, T>) -> Vec<(&u32, &T)> {
+    let mut components = components.iter().collect::<Vec<_>>();
+    components.sort_by_key(|&(id, _)| *id);
+    components
+}
+
+fn write_vectors(out: &mut String, name: &str, components: &HashMap<u32, Vector4<f32>>) {
+    for (id, v) in sorted(components) {
+        writeln!(out, "{} {} {:?} {:?} {:?} {:?}", name, id, v.x, v.y, v.z, v.w).unwrap();
+    }
+}
+
+fn write_scalars(out: &mut String, name: &str, components: &HashMap<u32, f32>) {
+    for (id, value) in sorted(components) {
+        writeln!(out, "{} {} {:?}", name, id, value).unwrap();
+    }
+}
+
+fn read_vector(record: &Record,
+               components: &mut HashMap<u32, Vector4<f32>>)
+               -> Result<(), String> {
+    let v = Vector4::new(record.get(1)?, record.get(2)?, record.get(3)?, record.get(4)?);
+    components.insert(record.get(0)?, v);
+    Ok(())
+}
+
+fn read_scalar(record: &Record, components: &mut HashMap<u32, f32>) -> Result<(), String> {
+    components.insert(record.get(0)?, record.get(1)?);
+    Ok(())
+}
@@ -0,0 +1,78 @@
+use super::entity::Kind;
+use super::entity::SaucerSize;
+use super::entity::Size;
+use super::tunables::Tunables;
+
+/// Which side an entity is on. Only what the player's side destroys scores points.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Faction {
+    Player,
+    Alien,
+    Neutral,
+}
+
+/// Kinds that collide the same way, indexing the rows and columns of `EFFECTS`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Layer {
+    Ship,
+    Shots,
+    HostileShots,
+    Asteroids,
+    Saucers,
+}
+
+/// What touching something does to an entity.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Effect {
+    Nothing,
+    /// Costs a life, unless the ship is invulnerable, and destroys it on its last life.
+    Damage,
+    Destroy,
+}
+
+const N: Effect = Effect::Nothing;
+const H: Effect = Effect::Damage;
+const D: Effect = Effect::Destroy;
+
+// What happens to an entity in the row's layer when it touches one in the column's layer:
+// ship, shots, hostile shots, asteroids and saucers
+const EFFECTS: [[Effect; 5]; 5] = [[N, N, H, H, H],
+                                   [N, N, N, D, D],
+                                   [D, N, N, D, N],
+                                   [N, D, D, N, D],
+                                   [D, D, N, D, N]];
+
+pub fn layer(kind: Kind) -> Layer {
+    match kind {
+        Kind::PlayerShip => Layer::Ship,
+        Kind::ProjectileFriendly => Layer::Shots,
+        Kind::ProjectileHostile => Layer::HostileShots,
+        Kind::Asteroid(_) => Layer::Asteroids,
+        Kind::Saucer(_) => Layer::Saucers,
+    }
+}
+
+pub fn faction(kind: Kind) -> Faction {
+    match kind {
+        Kind::PlayerShip | Kind::ProjectileFriendly => Faction::Player,
+        Kind::ProjectileHostile | Kind::Saucer(_) => Faction::Alien,
+        Kind::Asteroid(_) => Faction::Neutral,
+    }
+}
+
+/// What happens to an entity of kind `a` when it touches one of kind `b`.
+pub fn effect(a: Kind, b: Kind) -> Effect {
+    EFFECTS[layer(a) as usize][layer(b) as usize]
+}
+
+/// Points for the player's side destroying an entity of kind `kind`.
+pub fn points(kind: Kind, tunables: &Tunables) -> u32 {
+    match kind {
+        Kind::Asteroid(Size::Large) => tunables.large_asteroid_points,
+        Kind::Asteroid(Size::Medium) => tunables.medium_asteroid_points,
+        Kind::Asteroid(Size::Small) => tunables.small_asteroid_points,
+        Kind::Saucer(SaucerSize::Large) => tunables.large_saucer_points,
+        Kind::Saucer(SaucerSize::Small) => tunables.small_saucer_points,
+        _ => 0,
+    }
+}
@@ -0,0 +1,631 @@
+extern crate cgmath;
+
+use cgmath::Matrix;
+use cgmath::Matrix4;
+use cgmath::SquareMatrix;
+use super::models::Model;
+use super::render::DrawCommand;
+
+/// Every character that has a glyph. Lowercase letters are drawn as uppercase, and anything else
+/// as a question mark.
+pub const CHARACTERS: &'static str = "ABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789-_>/.,=[]';?";
+
+// Glyphs are on a 4 by 6 grid squeezed into the unit square, with commas hanging below it, and
+// are drawn as a line loop that retraces its strokes back to the start
+const GLYPH_A: [f32; 48] = [
+    -0.5,     0.5, 0.0, 1.0,
+    -0.5, -0.1667, 0.0, 1.0,
+    0.0,    -0.5, 0.0, 1.0,
+    0.5, -0.1667, 0.0, 1.0,
+    0.5,     0.5, 0.0, 1.0,
+    0.5,  0.1667, 0.0, 1.0,
+    -0.5,  0.1667, 0.0, 1.0,
+    0.5,  0.1667, 0.0, 1.0,
+    0.5,     0.5, 0.0, 1.0,
+    0.5, -0.1667, 0.0, 1.0,
+    0.0,    -0.5, 0.0, 1.0,
+    -0.5, -0.1667, 0.0, 1.0,
+];
+
+const GLYPH_B: [f32; 88] = [
+    -0.5,     0.5, 0.0, 1.0,
+    -0.5,    -0.5, 0.0, 1.0,
+    0.25,    -0.5, 0.0, 1.0,
+    0.5, -0.3333, 0.0, 1.0,
+    0.5, -0.1667, 0.0, 1.0,
+    0.25,     0.0, 0.0, 1.0,
+    -0.5,     0.0, 0.0, 1.0,
+    0.25,     0.0, 0.0, 1.0,
+    0.5,  0.1667, 0.0, 1.0,
+    0.5,  0.3333, 0.0, 1.0,
+    0.25,     0.5, 0.0, 1.0,
+    -0.5,     0.5, 0.0, 1.0,
+    0.25,     0.5, 0.0, 1.0,
+    0.5,  0.3333, 0.0, 1.0,
+    0.5,  0.1667, 0.0, 1.0,
+    0.25,     0.0, 0.0, 1.0,
+    -0.5,     0.0, 0.0, 1.0,
+    0.25,     0.0, 0.0, 1.0,
+    0.5, -0.1667, 0.0, 1.0,
+    0.5, -0.3333, 0.0, 1.0,
+    0.25,    -0.5, 0.0, 1.0,
+    -0.5,    -0.5, 0.0, 1.0,
+];
+
+const GLYPH_C: [f32; 24] = [
+    0.5,    -0.5, 0.0, 1.0,
+    -0.5,    -0.5, 0.0, 1.0,
+    -0.5,     0.5, 0.0, 1.0,
+    0.5,     0.5, 0.0, 1.0,
+    -0.5,     0.5, 0.0, 1.0,
+    -0.5,    -0.5, 0.0, 1.0,
+];
+
+const GLYPH_D: [f32; 48] = [
+    -0.5,     0.5, 0.0, 1.0,
+    -0.5,    -0.5, 0.0, 1.0,
+    0.0,    -0.5, 0.0, 1.0,
+    0.5, -0.1667, 0.0, 1.0,
+    0.5,  0.1667, 0.0, 1.0,
+    0.0,     0.5, 0.0, 1.0,
+    -0.5,     0.5, 0.0, 1.0,
+    0.0,     0.5, 0.0, 1.0,
+    0.5,  0.1667, 0.0, 1.0,
+    0.5, -0.1667, 0.0, 1.0,
+    0.0,    -0.5, 0.0, 1.0,
+    -0.5,    -0.5, 0.0, 1.0,
+];
+
+const GLYPH_E: [f32; 48] = [
+    0.5,    -0.5, 0.0, 1.0,
+    -0.5,    -0.5, 0.0, 1.0,
+    -0.5,     0.0, 0.0, 1.0,
+    0.25,     0.0, 0.0, 1.0,
+    -0.5,     0.0, 0.0, 1.0,
+    -0.5,     0.5, 0.0, 1.0,
+    0.5,     0.5, 0.0, 1.0,
+    -0.5,     0.5, 0.0, 1.0,
+    -0.5,     0.0, 0.0, 1.0,
+    0.25,     0.0, 0.0, 1.0,
+    -0.5,     0.0, 0.0, 1.0,
+    -0.5,    -0.5, 0.0, 1.0,
+];
+
+const GLYPH_F: [f32; 40] = [
+    0.5,    -0.5, 0.0, 1.0,
+    -0.5,    -0.5, 0.0, 1.0,
+    -0.5,     0.0, 0.0, 1.0,
+    0.25,     0.0, 0.0, 1.0,
+    -0.5,     0.0, 0.0, 1.0,
+    -0.5,     0.5, 0.0, 1.0,
+    -0.5,     0.0, 0.0, 1.0,
+    0.25,     0.0, 0.0, 1.0,
+    -0.5,     0.0, 0.0, 1.0,
+    -0.5,    -0.5, 0.0, 1.0,
+];
+
+const GLYPH_G: [f32; 48] = [
+    0.5, -0.3333, 0.0, 1.0,
+    0.5,    -0.5, 0.0, 1.0,
+    -0.5,    -0.5, 0.0, 1.0,
+    -0.5,     0.5, 0.0, 1.0,
+    0.5,     0.5, 0.0, 1.0,
+    0.5,     0.0, 0.0, 1.0,
+    0.0,     0.0, 0.0, 1.0,
+    0.5,     0.0, 0.0, 1.0,
+    0.5,     0.5, 0.0, 1.0,
+    -0.5,     0.5, 0.0, 1.0,
+    -0.5,    -0.5, 0.0, 1.0,
+    0.5,    -0.5, 0.0, 1.0,
+];
+
+const GLYPH_H: [f32; 40] = [
+    -0.5,    -0.5, 0.0, 1.0,
+    -0.5,     0.5, 0.0, 1.0,
+    -0.5,     0.0, 0.0, 1.0,
+    0.5,     0.0, 0.0, 1.0,
+    0.5,    -0.5, 0.0, 1.0,
+    0.5,     0.5, 0.0, 1.0,
+    0.5,    -0.5, 0.0, 1.0,
+    0.5,     0.0, 0.0, 1.0,
+    -0.5,     0.0, 0.0, 1.0,
+    -0.5,     0.5, 0.0, 1.0,
+];
+
+const GLYPH_I: [f32; 40] = [
+    -0.5,    -0.5, 0.0, 1.0,
+    0.5,    -0.5, 0.0, 1.0,
+    0.0,    -0.5, 0.0, 1.0,
+    0.0,     0.5, 0.0, 1.0,
+    -0.5,     0.5, 0.0, 1.0,
+    0.5,     0.5, 0.0, 1.0,
+    -0.5,     0.5, 0.0, 1.0,
+    0.0,     0.5, 0.0, 1.0,
+    0.0,    -0.5, 0.0, 1.0,
+    0.5,    -0.5, 0.0, 1.0,
+];
+
+const GLYPH_J: [f32; 24] = [
+    0.5,    -0.5, 0.0, 1.0,
+    0.5,     0.5, 0.0, 1.0,
+    0.0,     0.5, 0.0, 1.0,
+    -0.5,  0.1667, 0.0, 1.0,
+    0.0,     0.5, 0.0, 1.0,
+    0.5,     0.5, 0.0, 1.0,
+];
+
+const GLYPH_K: [f32; 40] = [
+    -0.5,    -0.5, 0.0, 1.0,
+    -0.5,     0.5, 0.0, 1.0,
+    -0.5,     0.0, 0.0, 1.0,
+    0.5,    -0.5, 0.0, 1.0,
+    -0.5,     0.0, 0.0, 1.0,
+    0.5,     0.5, 0.0, 1.0,
+    -0.5,     0.0, 0.0, 1.0,
+    0.5,    -0.5, 0.0, 1.0,
+    -0.5,     0.0, 0.0, 1.0,
+    -0.5,     0.5, 0.0, 1.0,
+];
+
+const GLYPH_L: [f32; 16] = [
+    -0.5,    -0.5, 0.0, 1.0,
+    -0.5,     0.5, 0.0, 1.0,
+    0.5,     0.5, 0.0, 1.0,
+    -0.5,     0.5, 0.0, 1.0,
+];
+
+const GLYPH_M: [f32; 32] = [
+    -0.5,     0.5, 0.0, 1.0,
+    -0.5,    -0.5, 0.0, 1.0,
+    0.0, -0.1667, 0.0, 1.0,
+    0.5,    -0.5, 0.0, 1.0,
+    0.5,     0.5, 0.0, 1.0,
+    0.5,    -0.5, 0.0, 1.0,
+    0.0, -0.1667, 0.0, 1.0,
+    -0.5,    -0.5, 0.0, 1.0,
+];
+
+const GLYPH_N: [f32; 24] = [
+    -0.5,     0.5, 0.0, 1.0,
+    -0.5,    -0.5, 0.0, 1.0,
+    0.5,     0.5, 0.0, 1.0,
+    0.5,    -0.5, 0.0, 1.0,
+    0.5,     0.5, 0.0, 1.0,
+    -0.5,    -0.5, 0.0, 1.0,
+];
+
+const GLYPH_O: [f32; 32] = [
+    -0.5,    -0.5, 0.0, 1.0,
+    0.5,    -0.5, 0.0, 1.0,
+    0.5,     0.5, 0.0, 1.0,
+    -0.5,     0.5, 0.0, 1.0,
+    -0.5,    -0.5, 0.0, 1.0,
+    -0.5,     0.5, 0.0, 1.0,
+    0.5,     0.5, 0.0, 1.0,
+    0.5,    -0.5, 0.0, 1.0,
+];
+
+const GLYPH_P: [f32; 32] = [
+    -0.5,     0.5, 0.0, 1.0,
+    -0.5,    -0.5, 0.0, 1.0,
+    0.5,    -0.5, 0.0, 1.0,
+    0.5,     0.0, 0.0, 1.0,
+    -0.5,     0.0, 0.0, 1.0,
+    0.5,     0.0, 0.0, 1.0,
+    0.5,    -0.5, 0.0, 1.0,
+    -0.5,    -0.5, 0.0, 1.0,
+];
+
+const GLYPH_Q: [f32; 56] = [
+    0.5,  0.1667, 0.0, 1.0,
+    0.5,    -0.5, 0.0, 1.0,
+    -0.5,    -0.5, 0.0, 1.0,
+    -0.5,     0.5, 0.0, 1.0,
+    0.0,     0.5, 0.0, 1.0,
+    0.5,  0.1667, 0.0, 1.0,
+    0.0,  0.1667, 0.0, 1.0,
+    0.5,     0.5, 0.0, 1.0,
+    0.0,  0.1667, 0.0, 1.0,
+    0.5,  0.1667, 0.0, 1.0,
+    0.0,     0.5, 0.0, 1.0,
+    -0.5,     0.5, 0.0, 1.0,
+    -0.5,    -0.5, 0.0, 1.0,
+    0.5,    -0.5, 0.0, 1.0,
+];
+
+const GLYPH_R: [f32; 48] = [
+    -0.5,     0.5, 0.0, 1.0,
+    -0.5,    -0.5, 0.0, 1.0,
+    0.5,    -0.5, 0.0, 1.0,
+    0.5,     0.0, 0.0, 1.0,
+    -0.5,     0.0, 0.0, 1.0,
+    -0.25,     0.0, 0.0, 1.0,
+    0.5,     0.5, 0.0, 1.0,
+    -0.25,     0.0, 0.0, 1.0,
+    -0.5,     0.0, 0.0, 1.0,
+    0.5,     0.0, 0.0, 1.0,
+    0.5,    -0.5, 0.0, 1.0,
+    -0.5,    -0.5, 0.0, 1.0,
+];
+
+const GLYPH_S: [f32; 40] = [
+    0.5,    -0.5, 0.0, 1.0,
+    -0.5,    -0.5, 0.0, 1.0,
+    -0.5,     0.0, 0.0, 1.0,
+    0.5,     0.0, 0.0, 1.0,
+    0.5,     0.5, 0.0, 1.0,
+    -0.5,     0.5, 0.0, 1.0,
+    0.5,     0.5, 0.0, 1.0,
+    0.5,     0.0, 0.0, 1.0,
+    -0.5,     0.0, 0.0, 1.0,
+    -0.5,    -0.5, 0.0, 1.0,
+];
+
+const GLYPH_T: [f32; 24] = [
+    -0.5,    -0.5, 0.0, 1.0,
+    0.5,    -0.5, 0.0, 1.0,
+    0.0,    -0.5, 0.0, 1.0,
+    0.0,     0.5, 0.0, 1.0,
+    0.0,    -0.5, 0.0, 1.0,
+    0.5,    -0.5, 0.0, 1.0,
+];
+
+const GLYPH_U: [f32; 24] = [
+    -0.5,    -0.5, 0.0, 1.0,
+    -0.5,     0.5, 0.0, 1.0,
+    0.5,     0.5, 0.0, 1.0,
+    0.5,    -0.5, 0.0, 1.0,
+    0.5,     0.5, 0.0, 1.0,
+    -0.5,     0.5, 0.0, 1.0,
+];
+
+const GLYPH_V: [f32; 16] = [
+    -0.5,    -0.5, 0.0, 1.0,
+    0.0,     0.5, 0.0, 1.0,
+    0.5,    -0.5, 0.0, 1.0,
+    0.0,     0.5, 0.0, 1.0,
+];
+
+const GLYPH_W: [f32; 32] = [
+    -0.5,    -0.5, 0.0, 1.0,
+    -0.5,     0.5, 0.0, 1.0,
+    0.0,  0.1667, 0.0, 1.0,
+    0.5,     0.5, 0.0, 1.0,
+    0.5,    -0.5, 0.0, 1.0,
+    0.5,     0.5, 0.0, 1.0,
+    0.0,  0.1667, 0.0, 1.0,
+    -0.5,     0.5, 0.0, 1.0,
+];
+
+const GLYPH_X: [f32; 32] = [
+    -0.5,    -0.5, 0.0, 1.0,
+    0.5,     0.5, 0.0, 1.0,
+    0.0,     0.0, 0.0, 1.0,
+    0.5,    -0.5, 0.0, 1.0,
+    -0.5,     0.5, 0.0, 1.0,
+    0.5,    -0.5, 0.0, 1.0,
+    0.0,     0.0, 0.0, 1.0,
+    0.5,     0.5, 0.0, 1.0,
+];
+
+const GLYPH_Y: [f32; 32] = [
+    -0.5,    -0.5, 0.0, 1.0,
+    0.0, -0.1667, 0.0, 1.0,
+    0.5,    -0.5, 0.0, 1.0,
+    0.0, -0.1667, 0.0, 1.0,
+    0.0,     0.5, 0.0, 1.0,
+    0.0, -0.1667, 0.0, 1.0,
+    0.5,    -0.5, 0.0, 1.0,
+    0.0, -0.1667, 0.0, 1.0,
+];
+
+const GLYPH_Z: [f32; 24] = [
+    -0.5,    -0.5, 0.0, 1.0,
+    0.5,    -0.5, 0.0, 1.0,
+    -0.5,     0.5, 0.0, 1.0,
+    0.5,     0.5, 0.0, 1.0,
+    -0.5,     0.5, 0.0, 1.0,
+    0.5,    -0.5, 0.0, 1.0,
+];
+
+const GLYPH_0: [f32; 40] = [
+    -0.5,    -0.5, 0.0, 1.0,
+    0.5,    -0.5, 0.0, 1.0,
+    0.5,     0.5, 0.0, 1.0,
+    -0.5,     0.5, 0.0, 1.0,
+    -0.5,    -0.5, 0.0, 1.0,
+    0.5,     0.5, 0.0, 1.0,
+    -0.5,    -0.5, 0.0, 1.0,
+    -0.5,     0.5, 0.0, 1.0,
+    0.5,     0.5, 0.0, 1.0,
+    0.5,    -0.5, 0.0, 1.0,
+];
+
+const GLYPH_1: [f32; 32] = [
+    -0.25, -0.3333, 0.0, 1.0,
+    0.0,    -0.5, 0.0, 1.0,
+    0.0,     0.5, 0.0, 1.0,
+    -0.5,     0.5, 0.0, 1.0,
+    0.5,     0.5, 0.0, 1.0,
+    -0.5,     0.5, 0.0, 1.0,
+    0.0,     0.5, 0.0, 1.0,
+    0.0,    -0.5, 0.0, 1.0,
+];
+
+const GLYPH_2: [f32; 40] = [
+    -0.5,    -0.5, 0.0, 1.0,
+    0.5,    -0.5, 0.0, 1.0,
+    0.5,     0.0, 0.0, 1.0,
+    -0.5,     0.0, 0.0, 1.0,
+    -0.5,     0.5, 0.0, 1.0,
+    0.5,     0.5, 0.0, 1.0,
+    -0.5,     0.5, 0.0, 1.0,
+    -0.5,     0.0, 0.0, 1.0,
+    0.5,     0.0, 0.0, 1.0,
+    0.5,    -0.5, 0.0, 1.0,
+];
+
+const GLYPH_3: [f32; 48] = [
+    -0.5,    -0.5, 0.0, 1.0,
+    0.5,    -0.5, 0.0, 1.0,
+    0.5,     0.0, 0.0, 1.0,
+    -0.25,     0.0, 0.0, 1.0,
+    0.5,     0.0, 0.0, 1.0,
+    0.5,     0.5, 0.0, 1.0,
+    -0.5,     0.5, 0.0, 1.0,
+    0.5,     0.5, 0.0, 1.0,
+    0.5,     0.0, 0.0, 1.0,
+    -0.25,     0.0, 0.0, 1.0,
+    0.5,     0.0, 0.0, 1.0,
+    0.5,    -0.5, 0.0, 1.0,
+];
+
+const GLYPH_4: [f32; 32] = [
+    -0.5,    -0.5, 0.0, 1.0,
+    -0.5,     0.0, 0.0, 1.0,
+    0.5,     0.0, 0.0, 1.0,
+    0.5,    -0.5, 0.0, 1.0,
+    0.5,     0.5, 0.0, 1.0,
+    0.5,    -0.5, 0.0, 1.0,
+    0.5,     0.0, 0.0, 1.0,
+    -0.5,     0.0, 0.0, 1.0,
+];
+
+const GLYPH_5: [f32; 40] = [
+    0.5,    -0.5, 0.0, 1.0,
+    -0.5,    -0.5, 0.0, 1.0,
+    -0.5,     0.0, 0.0, 1.0,
+    0.5,     0.0, 0.0, 1.0,
+    0.5,     0.5, 0.0, 1.0,
+    -0.5,     0.5, 0.0, 1.0,
+    0.5,     0.5, 0.0, 1.0,
+    0.5,     0.0, 0.0, 1.0,
+    -0.5,     0.0, 0.0, 1.0,
+    -0.5,    -0.5, 0.0, 1.0,
+];
+
+const GLYPH_6: [f32; 40] = [
+    0.5,    -0.5, 0.0, 1.0,
+    -0.5,    -0.5, 0.0, 1.0,
+    -0.5,     0.5, 0.0, 1.0,
+    0.5,     0.5, 0.0, 1.0,
+    0.5,     0.0, 0.0, 1.0,
+    -0.5,     0.0, 0.0, 1.0,
+    0.5,     0.0, 0.0, 1.0,
+    0.5,     0.5, 0.0, 1.0,
+    -0.5,     0.5, 0.0, 1.0,
+    -0.5,    -0.5, 0.0, 1.0,
+];
+
+const GLYPH_7: [f32; 16] = [
+    -0.5,    -0.5, 0.0, 1.0,
+    0.5,    -0.5, 0.0, 1.0,
+    0.5,     0.5, 0.0, 1.0,
+    0.5,    -0.5, 0.0, 1.0,
+];
+
+const GLYPH_8: [f32; 48] = [
+    -0.5,     0.0, 0.0, 1.0,
+    -0.5,    -0.5, 0.0, 1.0,
+    0.5,    -0.5, 0.0, 1.0,
+    0.5,     0.5, 0.0, 1.0,
+    -0.5,     0.5, 0.0, 1.0,
+    -0.5,     0.0, 0.0, 1.0,
+    0.5,     0.0, 0.0, 1.0,
+    -0.5,     0.0, 0.0, 1.0,
+    -0.5,     0.5, 0.0, 1.0,
+    0.5,     0.5, 0.0, 1.0,
+    0.5,    -0.5, 0.0, 1.0,
+    -0.5,    -0.5, 0.0, 1.0,
+];
+
+const GLYPH_9: [f32; 40] = [
+    0.5,     0.0, 0.0, 1.0,
+    -0.5,     0.0, 0.0, 1.0,
+    -0.5,    -0.5, 0.0, 1.0,
+    0.5,    -0.5, 0.0, 1.0,
+    0.5,     0.5, 0.0, 1.0,
+    -0.5,     0.5, 0.0, 1.0,
+    0.5,     0.5, 0.0, 1.0,
+    0.5,    -0.5, 0.0, 1.0,
+    -0.5,    -0.5, 0.0, 1.0,
+    -0.5,     0.0, 0.0, 1.0,
+];
+
+const GLYPH_MINUS: [f32; 8] = [
+    -0.25,     0.0, 0.0, 1.0,
+    0.25,     0.0, 0.0, 1.0,
+];
+
+const GLYPH_UNDERSCORE: [f32; 8] = [
+    -0.5,     0.5, 0.0, 1.0,
+    0.5,     0.5, 0.0, 1.0,
+];
+
+const GLYPH_GREATER: [f32; 16] = [
+    -0.25, -0.3333, 0.0, 1.0,
+    0.25,     0.0, 0.0, 1.0,
+    -0.25,  0.3333, 0.0, 1.0,
+    0.25,     0.0, 0.0, 1.0,
+];
+
+const GLYPH_SLASH: [f32; 8] = [
+    -0.5,     0.5, 0.0, 1.0,
+    0.5,    -0.5, 0.0, 1.0,
+];
+
+const GLYPH_PERIOD: [f32; 8] = [
+    0.0,  0.3333, 0.0, 1.0,
+    0.0,     0.5, 0.0, 1.0,
+];
+
+const GLYPH_COMMA: [f32; 8] = [
+    0.0,  0.3333, 0.0, 1.0,
+    -0.25,  0.6667, 0.0, 1.0,
+];
+
+const GLYPH_EQUALS: [f32; 24] = [
+    -0.5, -0.1667, 0.0, 1.0,
+    0.5, -0.1667, 0.0, 1.0,
+    0.5,  0.1667, 0.0, 1.0,
+    -0.5,  0.1667, 0.0, 1.0,
+    0.5,  0.1667, 0.0, 1.0,
+    0.5, -0.1667, 0.0, 1.0,
+];
+
+const GLYPH_LEFT_BRACKET: [f32; 24] = [
+    0.25,    -0.5, 0.0, 1.0,
+    -0.25,    -0.5, 0.0, 1.0,
+    -0.25,     0.5, 0.0, 1.0,
+    0.25,     0.5, 0.0, 1.0,
+    -0.25,     0.5, 0.0, 1.0,
+    -0.25,    -0.5, 0.0, 1.0,
+];
+
+const GLYPH_RIGHT_BRACKET: [f32; 24] = [
+    -0.25,    -0.5, 0.0, 1.0,
+    0.25,    -0.5, 0.0, 1.0,
+    0.25,     0.5, 0.0, 1.0,
+    -0.25,     0.5, 0.0, 1.0,
+    0.25,     0.5, 0.0, 1.0,
+    0.25,    -0.5, 0.0, 1.0,
+];
+
+const GLYPH_APOSTROPHE: [f32; 8] = [
+    0.0,    -0.5, 0.0, 1.0,
+    0.0, -0.1667, 0.0, 1.0,
+];
+
+const GLYPH_SEMICOLON: [f32; 24] = [
+    0.0, -0.1667, 0.0, 1.0,
+    0.0,     0.0, 0.0, 1.0,
+    0.0,  0.3333, 0.0, 1.0,
+    -0.25,  0.6667, 0.0, 1.0,
+    0.0,  0.3333, 0.0, 1.0,
+    0.0,     0.0, 0.0, 1.0,
+];
+
+const GLYPH_QUESTION: [f32; 48] = [
+    -0.5, -0.3333, 0.0, 1.0,
+    -0.25,    -0.5, 0.0, 1.0,
+    0.25,    -0.5, 0.0, 1.0,
+    0.5, -0.3333, 0.0, 1.0,
+    0.5, -0.1667, 0.0, 1.0,
+    0.0,     0.0, 0.0, 1.0,
+    0.0,  0.3333, 0.0, 1.0,
+    0.0,     0.0, 0.0, 1.0,
+    0.5, -0.1667, 0.0, 1.0,
+    0.5, -0.3333, 0.0, 1.0,
+    0.25,    -0.5, 0.0, 1.0,
+    -0.25,    -0.5, 0.0, 1.0,
+];
+
+/// The vertices of `character`'s glyph.
+pub fn glyph(character: char) -> &'static [f32] {
+    match character {
+        'A' => &GLYPH_A,
+        'B' => &GLYPH_B,
+        'C' => &GLYPH_C,
+        'D' => &GLYPH_D,
+        'E' => &GLYPH_E,
+        'F' => &GLYPH_F,
+        'G' => &GLYPH_G,
+        'H' => &GLYPH_H,
+        'I' => &GLYPH_I,
+        'J' => &GLYPH_J,
+        'K' => &GLYPH_K,
+        'L' => &GLYPH_L,
+        'M' => &GLYPH_M,
+        'N' => &GLYPH_N,
+        'O' => &GLYPH_O,
+        'P' => &GLYPH_P,
+        'Q' => &GLYPH_Q,
+        'R' => &GLYPH_R,
+        'S' => &GLYPH_S,
+        'T' => &GLYPH_T,
+        'U' => &GLYPH_U,
+        'V' => &GLYPH_V,
+        'W' => &GLYPH_W,
+        'X' => &GLYPH_X,
+        'Y' => &GLYPH_Y,
+        'Z' => &GLYPH_Z,
+        '0' => &GLYPH_0,
+        '1' => &GLYPH_1,
+        '2' => &GLYPH_2,
+        '3' => &GLYPH_3,
+        '4' => &GLYPH_4,
+        '5' => &GLYPH_5,
+        '6' => &GLYPH_6,
+        '7' => &GLYPH_7,
+        '8' => &GLYPH_8,
+        '9' => &GLYPH_9,
+        '-' => &GLYPH_MINUS,
+        '_' => &GLYPH_UNDERSCORE,
+        '>' => &GLYPH_GREATER,
+        '/' => &GLYPH_SLASH,
+        '.' => &GLYPH_PERIOD,
+        ',' => &GLYPH_COMMA,
+        '=' => &GLYPH_EQUALS,
+        '[' => &GLYPH_LEFT_BRACKET,
+        ']' => &GLYPH_RIGHT_BRACKET,
+        '\'' => &GLYPH_APOSTROPHE,
+        ';' => &GLYPH_SEMICOLON,
+        '?' => &GLYPH_QUESTION,
+        _ => &GLYPH_QUESTION,
+    }
+}
+
+/// Draw commands for a single line of `text`, with its top left corner at `(x, y)` and
+/// characters `size` units tall.
+pub fn text(projection: &Matrix4<f32>,
+            text: &str,
+            x: f32,
+            y: f32,
+            size: f32,
+            colour: [f32; 4])
+            -> Vec<DrawCommand> {
+    let width = size * 4.0 / 6.0;
+    let advance = size;
+    let mut commands = Vec::new();
+    for (i, character) in text.chars().enumerate() {
+        if character == ' ' {
+            continue;
+        }
+        let character = character.to_ascii_uppercase();
+        let character = if CHARACTERS.contains(character) { character } else { '?' };
+
+        let mut translation = Matrix4::one();
+        translation.replace_col(3,
+                                cgmath::vec4(x + advance * i as f32 + width / 2.0,
+                                               y + size / 2.0,
+                                               0.0,
+                                               1.0));
+        let scaling = Matrix4::from_diagonal(cgmath::vec4(width, size, 0.0, 1.0));
+        commands.push(DrawCommand {
+            model: Model::Glyph(character),
+            transform: projection.mul_m(&translation.mul_m(&scaling)),
+            colour: colour,
+        });
+    }
+    commands
+}
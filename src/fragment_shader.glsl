@@ -1,7 +1,9 @@
 #version 430 core
 
+layout(location = 2) uniform vec4 colour;
+
 out vec4 frag_color;
 
 void main() {
-    frag_color = vec4(1.0, 1.0, 1.0, 1.0);
+    frag_color = colour;
 }
@@ -0,0 +1,189 @@
+use std::fs::File;
+use std::io::Read;
+use std::io::Write;
+use std::path::Path;
+
+/// An RGB image in memory, top row first.
+#[derive(Clone)]
+pub struct Framebuffer {
+    width: usize,
+    height: usize,
+    pixels: Vec<u8>,
+}
+
+impl Framebuffer {
+    pub fn new(width: usize, height: usize) -> Framebuffer {
+        Framebuffer {
+            width: width,
+            height: height,
+            pixels: vec![0; width * height * 3],
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn pixels(&self) -> &[u8] {
+        &self.pixels
+    }
+
+    pub fn clear(&mut self, colour: [u8; 3]) {
+        for pixel in self.pixels.chunks_mut(3) {
+            pixel.copy_from_slice(&colour);
+        }
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> [u8; 3] {
+        let i = (y * self.width + x) * 3;
+        [self.pixels[i], self.pixels[i + 1], self.pixels[i + 2]]
+    }
+
+    /// Sets a pixel, ignoring coordinates outside the image.
+    pub fn set(&mut self, x: i32, y: i32, colour: [u8; 3]) {
+        if x < 0 || y < 0 || x as usize >= self.width || y as usize >= self.height {
+            return;
+        }
+        let i = (y as usize * self.width + x as usize) * 3;
+        self.pixels[i..i + 3].copy_from_slice(&colour);
+    }
+
+    /// Writes a PNG or a binary PPM, depending on the extension of `path`.
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        let bytes = match path.extension().and_then(|e| e.to_str()) {
+            Some("png") => self.to_png(),
+            Some("ppm") => self.to_ppm(),
+            _ => return Err(format!("Unsupported image format for {:?}.", path)),
+        };
+        let mut file = File::create(path).map_err(|e| e.to_string())?;
+        file.write_all(&bytes).map_err(|e| e.to_string())
+    }
+
+    /// Reads a binary PPM, the only format that can be loaded back.
+    pub fn load(path: &Path) -> Result<Framebuffer, String> {
+        let mut file = File::open(path).map_err(|e| e.to_string())?;
+        let mut bytes = Vec::new();
+        file.read_to_end(&mut bytes).map_err(|e| e.to_string())?;
+        Framebuffer::from_ppm(&bytes)
+    }
+
+    pub fn from_ppm(bytes: &[u8]) -> Result<Framebuffer, String> {
+        // The header is four whitespace separated fields followed by a single whitespace byte
+        let mut fields = Vec::new();
+        let mut i = 0;
+        while fields.len() < 4 {
+            while i < bytes.len() && (bytes[i] as char).is_whitespace() {
+                i += 1;
+            }
+            let start = i;
+            while i < bytes.len() && !(bytes[i] as char).is_whitespace() {
+                i += 1;
+            }
+            if start == i {
+                return Err("Truncated PPM header.".to_string());
+            }
+            fields.push(String::from_utf8_lossy(&bytes[start..i]).into_owned());
+        }
+        if fields[0] != "P6" || fields[3] != "255" {
+            return Err("Only 8 bit binary PPM images are supported.".to_string());
+        }
+        let width = fields[1].parse::<usize>().map_err(|e| e.to_string())?;
+        let height = fields[2].parse::<usize>().map_err(|e| e.to_string())?;
+        let pixels = if i < bytes.len() { &bytes[i + 1..] } else { &[] };
+        if pixels.len() != width * height * 3 {
+            return Err(format!("Expected {} bytes of pixel data, found {}.",
+                               width * height * 3,
+                               pixels.len()));
+        }
+        Ok(Framebuffer {
+            width: width,
+            height: height,
+            pixels: pixels.to_vec(),
+        })
+    }
+
+    pub fn to_ppm(&self) -> Vec<u8> {
+        let mut bytes = format!("P6\n{} {}\n255\n", self.width, self.height).into_bytes();
+        bytes.extend_from_slice(&self.pixels);
+        bytes
+    }
+
+    /// Encodes the image as a PNG with uncompressed deflate blocks, which keeps the encoder
+    /// small at the cost of file size.
+    pub fn to_png(&self) -> Vec<u8> {
+        let mut scanlines = Vec::with_capacity((self.width * 3 + 1) * self.height);
+        for row in self.pixels.chunks(self.width * 3) {
+            // Filter type 0, no filtering
+            scanlines.push(0);
+            scanlines.extend_from_slice(row);
+        }
+
+        let mut header = Vec::new();
+        push_u32(&mut header, self.width as u32);
+        push_u32(&mut header, self.height as u32);
+        // 8 bits per channel, colour type 2 (RGB), default compression, filtering and no interlace
+        header.extend_from_slice(&[8, 2, 0, 0, 0]);
+
+        let mut png = vec![0x89, b'P', b'N', b'G', 0x0d, 0x0a, 0x1a, 0x0a];
+        push_chunk(&mut png, b"IHDR", &header);
+        push_chunk(&mut png, b"IDAT", &zlib_stored(&scanlines));
+        push_chunk(&mut png, b"IEND", &[]);
+        png
+    }
+}
+
+fn push_u32(bytes: &mut Vec<u8>, value: u32) {
+    bytes.extend_from_slice(&[(value >> 24) as u8, (value >> 16) as u8, (value >> 8) as u8,
+                              value as u8]);
+}
+
+fn push_chunk(png: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    push_u32(png, data.len() as u32);
+    let start = png.len();
+    png.extend_from_slice(kind);
+    png.extend_from_slice(data);
+    let crc = crc32(&png[start..]);
+    push_u32(png, crc);
+}
+
+fn zlib_stored(data: &[u8]) -> Vec<u8> {
+    // Deflate with a 32K window, no preset dictionary
+    let mut out = vec![0x78, 0x01];
+    let mut blocks = data.chunks(0xffff).peekable();
+    if blocks.peek().is_none() {
+        out.extend_from_slice(&[1, 0, 0, 0xff, 0xff]);
+    }
+    while let Some(block) = blocks.next() {
+        let last = if blocks.peek().is_none() { 1 } else { 0 };
+        let len = block.len() as u16;
+        out.push(last);
+        out.extend_from_slice(&[len as u8, (len >> 8) as u8, !len as u8, (!len >> 8) as u8]);
+        out.extend_from_slice(block);
+    }
+    push_u32(&mut out, adler32(data));
+    out
+}
+
+fn crc32(data: &[u8]) -> u32 {
+    let mut crc = 0xffffffffu32;
+    for &byte in data {
+        crc ^= byte as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 == 1 { (crc >> 1) ^ 0xedb88320 } else { crc >> 1 };
+        }
+    }
+    !crc
+}
+
+fn adler32(data: &[u8]) -> u32 {
+    let (mut a, mut b) = (1u32, 0u32);
+    for &byte in data {
+        a = (a + byte as u32) % 65521;
+        b = (b + a) % 65521;
+    }
+    (b << 16) | a
+}
@@ -0,0 +1,146 @@
+extern crate gl;
+extern crate libc;
+
+use gl::types::*;
+use std::collections::HashMap;
+use std::ffi::CString;
+use std::mem;
+use std::ptr;
+use std::str;
+use super::font;
+use super::models::Model;
+use super::models::MODELS;
+use super::render::DrawCommand;
+use super::render::Renderer;
+
+/// Draws with OpenGL into whatever context is current when it is created.
+pub struct GlRenderer {
+    objects: HashMap<Model, (u32, i32)>,
+}
+
+impl GlRenderer {
+    pub fn new(width: i32, height: i32) -> GlRenderer {
+        let vs = include_str!("vertex_shader.glsl");
+        let fs = include_str!("fragment_shader.glsl");
+        let program = create_program(vs, fs);
+
+        unsafe {
+            gl::ClearColor(0.0, 0.0, 0.0, 1.0);
+            gl::Viewport(0, 0, width, height);
+            gl::UseProgram(program);
+        }
+
+        let mut objects = HashMap::new();
+        let glyphs = font::CHARACTERS.chars().map(Model::Glyph);
+        for model in MODELS.iter().cloned().chain(glyphs) {
+            let vao = create_object(0, model.vertices());
+            objects.insert(model, (vao, model.vertex_count() as i32));
+        }
+
+        GlRenderer { objects: objects }
+    }
+
+    /// Stretches the picture over a window that changed size.
+    pub fn resize(&mut self, width: i32, height: i32) {
+        unsafe {
+            gl::Viewport(0, 0, width, height);
+        }
+    }
+}
+
+impl Renderer for GlRenderer {
+    fn draw(&mut self, commands: &[DrawCommand]) {
+        unsafe {
+            gl::Clear(gl::COLOR_BUFFER_BIT);
+        }
+
+        for command in commands {
+            let (vao, vertices) = *self.objects.get(&command.model).unwrap();
+            let mvp_array: [f32; 16] = *command.transform.as_ref();
+            unsafe {
+                gl::BindVertexArray(vao);
+                gl::UniformMatrix4fv(1, 1, gl::FALSE, mvp_array.as_ptr());
+                gl::Uniform4fv(2, 1, command.colour.as_ptr());
+                gl::DrawArrays(gl::LINE_LOOP, 0, vertices);
+            }
+        }
+    }
+}
+
+fn create_shader(shader_source: &str, shader_type: GLenum) -> u32 {
+    let c_str = CString::new(shader_source.as_bytes())
+                    .expect("Could not create c string from shader source.");
+
+    unsafe {
+        let shader = gl::CreateShader(shader_type);
+        gl::ShaderSource(shader, 1, &c_str.as_ptr(), ptr::null());
+        gl::CompileShader(shader);
+
+        let mut status = gl::FALSE as GLint;
+        gl::GetShaderiv(shader, gl::COMPILE_STATUS, &mut status);
+        if status != (gl::TRUE as GLint) {
+            let mut length = 0;
+            gl::GetShaderiv(shader, gl::INFO_LOG_LENGTH, &mut length);
+            let mut message = Vec::with_capacity(length as usize);
+            message.set_len((length as usize) - 1);
+            gl::GetShaderInfoLog(shader,
+                                 length,
+                                 ptr::null_mut(),
+                                 message.as_mut_ptr() as *mut GLchar);
+            panic!("{}",
+                   str::from_utf8(&message).ok().expect("ShaderInfoLog not valid utf8."));
+        }
+        shader
+    }
+}
+
+fn create_program(vs_src: &str, fs_src: &str) -> u32 {
+    unsafe {
+        let program = gl::CreateProgram();
+        let vs = create_shader(vs_src, gl::VERTEX_SHADER);
+        let fs = create_shader(fs_src, gl::FRAGMENT_SHADER);
+        gl::AttachShader(program, vs);
+        gl::AttachShader(program, fs);
+        gl::LinkProgram(program);
+
+        let mut success = 0;
+        gl::GetProgramiv(program, gl::LINK_STATUS, &mut success);
+        if success == gl::FALSE as i32 {
+            let mut length = 0;
+            gl::GetProgramiv(program, gl::INFO_LOG_LENGTH, &mut length);
+            let mut log = Vec::with_capacity(length as usize);
+            log.set_len(length as usize - 1);
+            gl::GetProgramInfoLog(program,
+                                  length,
+                                  ptr::null_mut(),
+                                  log.as_mut_ptr() as *mut gl::types::GLchar);
+            panic!("{}",
+                   str::from_utf8(&log).ok().expect("ProgramInfoLog not valid utf8."));
+        }
+
+        gl::DetachShader(program, vs);
+        gl::DetachShader(program, fs);
+
+        program
+    }
+}
+
+fn create_object(location: u32, vertices: &[f32]) -> u32 {
+    unsafe {
+        let mut vao = 0;
+        gl::GenVertexArrays(1, &mut vao);
+        gl::BindVertexArray(vao);
+
+        let mut vbo = 0;
+        gl::GenBuffers(1, &mut vbo);
+        gl::BindBuffer(gl::ARRAY_BUFFER, vbo);
+        gl::BufferData(gl::ARRAY_BUFFER,
+                       (vertices.len() * mem::size_of::<GLfloat>()) as GLsizeiptr,
+                       mem::transmute(&vertices[0]),
+                       gl::STATIC_DRAW);
+        gl::VertexAttribPointer(location, 4, gl::FLOAT, gl::FALSE, 0, ptr::null());
+        gl::EnableVertexAttribArray(location);
+
+        vao
+    }
+}
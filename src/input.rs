@@ -0,0 +1,169 @@
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use super::controller::Control;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Action {
+    Thrust,
+    RotateLeft,
+    RotateRight,
+    Fire,
+    Hyperspace,
+    Pause,
+    Quit,
+}
+
+pub const ACTIONS: [Action; 7] = [Action::Thrust,
+                                  Action::RotateLeft,
+                                  Action::RotateRight,
+                                  Action::Fire,
+                                  Action::Hyperspace,
+                                  Action::Pause,
+                                  Action::Quit];
+
+impl Action {
+    pub fn name(&self) -> &'static str {
+        match *self {
+            Action::Thrust => "thrust",
+            Action::RotateLeft => "rotate_left",
+            Action::RotateRight => "rotate_right",
+            Action::Fire => "fire",
+            Action::Hyperspace => "hyperspace",
+            Action::Pause => "pause",
+            Action::Quit => "quit",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Action> {
+        ACTIONS.iter().cloned().find(|action| action.name() == name)
+    }
+}
+
+/// What a frontend reports for one tick: the actions that are down at the end of it, and the
+/// ones that were pressed at any point during it, so that a tap shorter than a tick still counts.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Input {
+    pub down: BTreeSet<Action>,
+    pub tapped: BTreeSet<Action>,
+    /// The point in world coordinates the ship turns towards, when aiming with the mouse.
+    pub aim: Option<(f32, f32)>,
+}
+
+impl Input {
+    pub fn new() -> Input {
+        Input {
+            down: BTreeSet::new(),
+            tapped: BTreeSet::new(),
+            aim: None,
+        }
+    }
+
+    pub fn press(&mut self, action: Action) {
+        self.down.insert(action);
+        self.tapped.insert(action);
+    }
+
+    pub fn release(&mut self, action: Action) {
+        self.down.remove(&action);
+    }
+
+    /// Forgets the taps once a tick has seen them, keeping what is still held down.
+    pub fn next_tick(&mut self) {
+        self.tapped.clear();
+    }
+}
+
+/// A key, mouse button or game controller control that can hold an action down.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Source {
+    Key(String),
+    Mouse(String),
+    /// A control of the game controller with the given SDL2 instance id.
+    Controller(i32, Control),
+}
+
+/// The sources holding each action down, across every device, so that letting go of one key
+/// or button leaves the action down while another source still holds it.
+pub struct Sources {
+    held: BTreeMap<Source, Action>,
+}
+
+impl Sources {
+    pub fn new() -> Sources {
+        Sources { held: BTreeMap::new() }
+    }
+
+    /// Presses or lets go of `action` from `source`, changing `input` when the first source
+    /// presses the action or the last one lets go of it.
+    ///
+    /// A source pressing again without letting go in between presses the action again, as the
+    /// frontend lost its release.
+    pub fn set(&mut self, source: Source, action: Action, down: bool, input: &mut Input) {
+        if down {
+            let again = self.held.insert(source, action).is_some();
+            if again || self.count(action) == 1 {
+                input.press(action);
+            }
+        } else if self.held.remove(&source).is_some() && self.count(action) == 0 {
+            input.release(action);
+        }
+    }
+
+    pub fn holds(&self, source: &Source) -> bool {
+        self.held.contains_key(source)
+    }
+
+    /// Lets go of everything held by the sources `which` picks out, such as the controls of an
+    /// unplugged controller.
+    pub fn release_all<F>(&mut self, which: F, input: &mut Input)
+        where F: Fn(&Source) -> bool
+    {
+        let sources = self.held.keys().filter(|source| which(source)).cloned().collect::<Vec<_>>();
+        for source in sources {
+            let action = self.held[&source];
+            self.set(source, action, false, input);
+        }
+    }
+
+    fn count(&self, action: Action) -> usize {
+        self.held.values().filter(|&&held| held == action).count()
+    }
+}
+
+/// The pressed, held and released edges of every action over one tick.
+///
+/// Edges come from comparing what is down against the previous tick, so a lost key up or key
+/// down event only affects the ticks until the frontend reports the key again.
+pub struct Actions {
+    pressed: BTreeSet<Action>,
+    held: BTreeSet<Action>,
+    released: BTreeSet<Action>,
+}
+
+impl Actions {
+    pub fn new(previous: &BTreeSet<Action>, input: &Input) -> Actions {
+        let mut pressed = input.down.difference(previous).cloned().collect::<BTreeSet<_>>();
+        pressed.extend(input.tapped.iter().cloned());
+        let mut released = previous.difference(&input.down).cloned().collect::<BTreeSet<_>>();
+        // A tap that was let go of before the end of the tick is released straight away
+        released.extend(input.tapped.difference(&input.down).cloned());
+        Actions {
+            pressed: pressed,
+            held: input.down.clone(),
+            released: released,
+        }
+    }
+
+    pub fn pressed(&self, action: Action) -> bool {
+        self.pressed.contains(&action)
+    }
+
+    /// Down at the end of the tick, or tapped during it.
+    pub fn held(&self, action: Action) -> bool {
+        self.held.contains(&action) || self.pressed.contains(&action)
+    }
+
+    pub fn released(&self, action: Action) -> bool {
+        self.released.contains(&action)
+    }
+}
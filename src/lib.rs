@@ -0,0 +1,42 @@
+//! The Asteroids simulation, usable without a window.
+//!
+//! Create a game with `Asteroids::new`, advance it with `update` and an `Input` of the actions
+//! being pressed, and inspect it through `Asteroids::entities` and `Asteroids::state`. The SDL2
+//! frontend lives in `main.rs`.
+
+extern crate cgmath;
+extern crate gl;
+extern crate libc;
+extern crate rand;
+extern crate time;
+
+pub mod asteroids;
+pub mod bindings;
+pub mod collisions;
+pub mod controller;
+pub mod entity;
+pub mod factions;
+pub mod font;
+pub mod framebuffer;
+pub mod gl_renderer;
+pub mod input;
+pub mod models;
+pub mod physics;
+pub mod render;
+pub mod replay;
+pub mod settings;
+pub mod snapshot;
+pub mod software_renderer;
+pub mod spatial_hash;
+pub mod terminal;
+pub mod tunables;
+
+pub use asteroids::Asteroids;
+pub use asteroids::update;
+pub use entity::Entity;
+pub use entity::EntityState;
+pub use entity::Kind;
+pub use entity::SaucerSize;
+pub use entity::Size;
+pub use input::Action;
+pub use input::Input;
@@ -1,26 +1,322 @@
-extern crate cgmath;
+extern crate asteroids;
 extern crate gl;
 extern crate libc;
 extern crate sdl2;
 extern crate time;
 
-mod asteroids;
-mod collisions;
-mod entity;
-mod render;
-
+use asteroids::Asteroids;
+use asteroids::bindings::Binding;
+use asteroids::bindings::Bindings;
+use asteroids::controller::Axis;
+use asteroids::controller::ControllerEvent;
+use asteroids::gl_renderer::GlRenderer;
+use asteroids::input::Action;
+use asteroids::input::Input;
+use asteroids::input::Source;
+use asteroids::input::Sources;
+use asteroids::physics::Handling;
+use asteroids::physics::Integrator;
+use asteroids::software_renderer::SoftwareRenderer;
+use asteroids::terminal::RawTerminal;
+use asteroids::terminal::TerminalInput;
+use asteroids::terminal::TerminalRenderer;
+use asteroids::tunables::Tunables;
+use asteroids::tunables::TunablesWatcher;
+use asteroids::replay::Replay;
+use asteroids::settings::Settings;
+use asteroids::snapshot;
+use sdl2::controller::Button;
+use sdl2::controller;
 use sdl2::event::Event;
+use sdl2::event::WindowEventId;
 use sdl2::keyboard::Scancode;
+use sdl2::mouse::Mouse;
 use std::collections::HashMap;
+use std::env;
+use std::path::Path;
+use std::path::PathBuf;
+use std::process;
+use std::thread;
+use std::time::Duration;
+
+const TICK_RATE: u32 = 60;
+// Longest wall-clock frame fed into the simulation, so a stall doesn't trigger a burst of ticks
+const MAX_FRAME_TIME: f32 = 0.25;
+const QUICKSAVE_PATH: &'static str = "quicksave.txt";
+const BINDINGS_PATH: &'static str = "bindings.txt";
+const TUNABLES_PATH: &'static str = "tunables.txt";
+
+struct Options {
+    headless: bool,
+    terminal: bool,
+    ticks: Option<u32>,
+    seed: u64,
+    record: Option<PathBuf>,
+    replay: Option<PathBuf>,
+    load: Option<PathBuf>,
+    save: Option<PathBuf>,
+    dump_frames: Option<PathBuf>,
+    bindings: Option<PathBuf>,
+    tunables: Option<PathBuf>,
+    mouse: bool,
+    integrator: Integrator,
+    handling: Handling,
+}
+
+fn parse_options() -> Options {
+    let mut options = Options {
+        headless: false,
+        terminal: false,
+        ticks: None,
+        seed: time::precise_time_ns(),
+        record: None,
+        replay: None,
+        load: None,
+        save: None,
+        dump_frames: None,
+        bindings: None,
+        tunables: None,
+        mouse: false,
+        integrator: Integrator::VelocityVerlet,
+        handling: Handling::Classic,
+    };
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match &arg[..] {
+            "--headless" => options.headless = true,
+            "--terminal" => options.terminal = true,
+            "--mouse" => options.mouse = true,
+            "--ticks" => {
+                let ticks = args.next().expect("Missing value for --ticks.");
+                options.ticks = Some(ticks.parse().expect("Could not parse --ticks as a number."));
+            }
+            "--seed" => {
+                let seed = args.next().expect("Missing value for --seed.");
+                options.seed = seed.parse().expect("Could not parse --seed as a number.");
+            }
+            "--record" => {
+                let path = args.next().expect("Missing file for --record.");
+                options.record = Some(PathBuf::from(path));
+            }
+            "--replay" => {
+                let path = args.next().expect("Missing file for --replay.");
+                options.replay = Some(PathBuf::from(path));
+            }
+            "--load" => {
+                let path = args.next().expect("Missing file for --load.");
+                options.load = Some(PathBuf::from(path));
+            }
+            "--save" => {
+                let path = args.next().expect("Missing file for --save.");
+                options.save = Some(PathBuf::from(path));
+            }
+            "--dump-frames" => {
+                let path = args.next().expect("Missing directory for --dump-frames.");
+                options.dump_frames = Some(PathBuf::from(path));
+            }
+            "--integrator" => {
+                let name = args.next().expect("Missing value for --integrator.");
+                options.integrator = Integrator::from_name(&name)
+                                         .expect("Unknown --integrator, expected euler or verlet.");
+            }
+            "--handling" => {
+                let name = args.next().expect("Missing value for --handling.");
+                options.handling = Handling::from_name(&name)
+                                       .expect("Unknown --handling, expected classic or arcade.");
+            }
+            "--tunables" => {
+                let path = args.next().expect("Missing file for --tunables.");
+                options.tunables = Some(PathBuf::from(path));
+            }
+            "--bindings" => {
+                let path = args.next().expect("Missing file for --bindings.");
+                options.bindings = Some(PathBuf::from(path));
+            }
+            _ => panic!("Unknown argument: {}", arg),
+        }
+    }
+    if options.load.is_some() && (options.record.is_some() || options.replay.is_some()) {
+        panic!("--load can not be combined with --record or --replay.");
+    }
+    options
+}
 
 fn main() {
+    let options = parse_options();
+
+    let playback = options.replay.as_ref().map(|path| {
+        Replay::load(path).unwrap_or_else(|e| panic!("Could not load replay {:?}: {}", path, e))
+    });
+    let (seed, tick_rate, integrator, handling) = match playback {
+        Some(ref replay) => {
+            (replay.seed(), replay.tick_rate(), replay.integrator(), replay.handling())
+        }
+        None => (options.seed, TICK_RATE, options.integrator, options.handling),
+    };
+    println!("Seed: {}", seed);
+
+    // Like the bindings, the default tunables file is optional
+    let tunables_path = options.tunables.clone().unwrap_or(PathBuf::from(TUNABLES_PATH));
+    let tunables = match playback {
+        Some(ref replay) => replay.tunables().clone(),
+        None if options.tunables.is_some() || tunables_path.exists() => {
+            Tunables::load(&tunables_path).unwrap_or_else(|e| {
+                panic!("Could not load tunables {:?}: {}", tunables_path, e)
+            })
+        }
+        None => Tunables::new(),
+    };
+    // Replays must run with the tunables they were recorded with from start to end
+    let mut watcher = if playback.is_none() && options.record.is_none() {
+        Some(TunablesWatcher::new(&tunables_path))
+    } else {
+        None
+    };
+
+    let mut recording = options.record.as_ref().map(|_| {
+        Replay::new(seed, tick_rate, integrator, handling, tunables.clone())
+    });
+    let mut asteroids = match options.load {
+        Some(ref path) => {
+            snapshot::load(path)
+                .unwrap_or_else(|e| panic!("Could not load snapshot {:?}: {}", path, e))
+        }
+        None => {
+            let mut asteroids = Asteroids::with_tunables(seed, tunables);
+            asteroids.set_integrator(integrator);
+            asteroids.set_handling(handling);
+            asteroids
+        }
+    };
+    let ticks = if options.headless {
+        run_headless(&mut asteroids,
+                     tick_rate,
+                     options.ticks,
+                     options.dump_frames.as_ref().map(|p| p.as_path()),
+                     playback.as_ref(),
+                     recording.as_mut())
+    } else if options.terminal {
+        run_terminal(&mut asteroids,
+                     tick_rate,
+                     watcher.as_mut(),
+                     playback.as_ref(),
+                     recording.as_mut())
+    } else {
+        // The default bindings file is optional, one given on the command line is not
+        let path = options.bindings.clone().unwrap_or(PathBuf::from(BINDINGS_PATH));
+        let bindings = if options.bindings.is_some() || path.exists() {
+            Bindings::load(&path)
+                .unwrap_or_else(|e| panic!("Could not load bindings {:?}: {}", path, e))
+        } else {
+            Bindings::defaults()
+        };
+        let keymap = KeyMap::new(bindings)
+                         .unwrap_or_else(|e| panic!("Invalid bindings {:?}: {}", path, e));
+        run_windowed(&mut asteroids,
+                     tick_rate,
+                     keymap,
+                     &path,
+                     options.mouse,
+                     watcher.as_mut(),
+                     playback.as_ref(),
+                     recording.as_mut())
+    };
+
+    println!("Ticks: {}", ticks);
+    println!("Stage: {}", asteroids.stage());
+    println!("Score: {}", asteroids.score());
+    println!("Lives: {}", asteroids.lives());
+
+    if let Some(path) = options.save {
+        snapshot::save(&asteroids, &path)
+            .unwrap_or_else(|e| panic!("Could not save snapshot {:?}: {}", path, e));
+    }
+
+    if let (Some(path), Some(mut recording)) = (options.record, recording) {
+        recording.finish(asteroids.checksum());
+        recording.save(&path).unwrap_or_else(|e| panic!("Could not save replay {:?}: {}", path, e));
+    }
+
+    if let Some(replay) = playback {
+        if ticks < replay.ticks() {
+            println!("Replay stopped after {} of {} ticks.", ticks, replay.ticks());
+        } else if let Err(e) = replay.verify(asteroids.checksum()) {
+            println!("{}", e);
+            process::exit(1);
+        } else {
+            println!("Replay verified.");
+        }
+    }
+}
+
+/// Runs a single tick, with input from the replay being played back if there is one.
+fn step(asteroids: &mut Asteroids,
+        live_input: &Input,
+        dt: f32,
+        tick: usize,
+        playback: Option<&Replay>,
+        recording: Option<&mut Replay>) {
+    let input = match playback {
+        Some(replay) => replay.input(tick),
+        None => live_input,
+    };
+    if let Some(recording) = recording {
+        recording.record(input);
+    }
+    asteroids::update(asteroids, input, dt);
+}
+
+fn finished(asteroids: &Asteroids, tick: usize, playback: Option<&Replay>) -> bool {
+    !asteroids.should_continue() || playback.map_or(false, |replay| tick >= replay.ticks())
+}
+
+fn run_headless(asteroids: &mut Asteroids,
+                tick_rate: u32,
+                ticks: Option<u32>,
+                dump_frames: Option<&Path>,
+                playback: Option<&Replay>,
+                mut recording: Option<&mut Replay>)
+                -> usize {
+    let input = Input::new();
+    let mut renderer = SoftwareRenderer::new(800, 600);
+    let dt = 1.0 / tick_rate as f32;
+    // A replay runs for as long as it was recorded
+    let ticks = if playback.is_some() { None } else { ticks };
+
+    let mut tick = 0;
+    while !finished(asteroids, tick, playback) && ticks.map_or(true, |t| tick < t as usize) {
+        step(asteroids, &input, dt, tick, playback, recording.as_mut().map(|r| &mut **r));
+        tick += 1;
+        if let Some(directory) = dump_frames {
+            asteroids::asteroids::render(asteroids, 1.0, &mut renderer);
+            let path = directory.join(format!("frame-{:06}.png", tick));
+            renderer.framebuffer()
+                    .save(&path)
+                    .unwrap_or_else(|e| panic!("Could not save frame {:?}: {}", path, e));
+        }
+    }
+    tick
+}
+
+fn run_windowed(asteroids: &mut Asteroids,
+                tick_rate: u32,
+                mut keymap: KeyMap,
+                bindings_path: &Path,
+                mouse: bool,
+                mut watcher: Option<&mut TunablesWatcher>,
+                playback: Option<&Replay>,
+                mut recording: Option<&mut Replay>)
+                -> usize {
     let context = sdl2::init().expect("Could not initialize SDL2.");
     let mut events = context.event_pump()
         .expect("Could not create SDL2 event pump.");
     let video = context.video()
         .expect("Could not create SDL2 video subsystem.");
+    let game_controller = context.game_controller()
+        .expect("Could not create SDL2 game controller subsystem.");
     let window = video.window("Asteroids", 800, 600)
         .opengl()
+        .resizable()
         .position_centered()
         .build()
         .expect("Could not build SDL2 window.");
@@ -30,82 +326,356 @@ fn main() {
         .expect("Could not make OpenGL context current.");
     gl::load_with(|s| video.gl_get_proc_address(s) as *const libc::c_void);
 
-    let vs = include_str!("vertex_shader.glsl");
-    let fs = include_str!("fragment_shader.glsl");
-    let program = render::create_program(vs, fs);
-
-    unsafe {
-        gl::ClearColor(0.0, 0.0, 0.0, 1.0);
-        gl::Viewport(0, 0, 800, 600);
-        gl::UseProgram(program);
-    }
-
-    let player_ship = vec![
-        0.0, -0.5, 0.0, 1.0,
-        -0.5,  0.5, 0.0, 1.0,
-        0.5,  0.5, 0.0, 1.0,
-    ];
-    render::create_object(0, &player_ship);
-
-    let projectile = vec![
-        -0.5, -0.5, 0.0, 1.0,
-        -0.5,  0.5, 0.0, 1.0,
-        0.5,  0.5, 0.0, 1.0,
-        0.5, -0.5, 0.0, 1.0,
-    ];
-    render::create_object(0, &projectile);
-
-    let asteroid_1 = vec![
-        0.1, -0.5, 0.0, 1.0,
-        -0.4, -0.3, 0.0, 1.0,
-        -0.2, -0.1, 0.0, 1.0,
-        -0.5,  0.0, 0.0, 1.0,
-        -0.4,  0.4, 0.0, 1.0,
-        -0.1,  0.5, 0.0, 1.0,
-        0.3,  0.2, 0.0, 1.0,
-        0.2,  0.1, 0.0, 1.0,
-        0.4, -0.2, 0.0, 1.0,
-        0.4, -0.3, 0.0, 1.0,
-    ];
-    render::create_object(0, &asteroid_1);
-
-    let mut asteroids = asteroids::Asteroids::new();
+    let mut renderer = GlRenderer::new(800, 600);
 
+    let dt = 1.0 / tick_rate as f32;
+    let mut tick = 0;
+    let mut accumulator = 0.0;
+    // Taps are kept until a tick consumes them, since a fast frame may not run any ticks
+    let mut input = Input::new();
+    // The game stands still while the settings screen is open
+    let mut settings: Option<Settings> = None;
+    // SDL2 reports controllers that are plugged in at startup as added too
+    let mut open_controllers = Vec::new();
+    let mut sources = Sources::new();
     let mut current_time = time::precise_time_ns();
-    while asteroids.should_continue() {
+    while !finished(asteroids, tick, playback) {
         let previous_time = current_time;
         current_time = time::precise_time_ns();
         let delta = (current_time - previous_time) as f32 / 1_000_000_000.0;
-        let mut input = HashMap::new();
+        accumulator += delta.min(MAX_FRAME_TIME);
         for event in events.poll_iter() {
-            let c = translate_sdl2_event(event);
-            let transitions = input.entry(c).or_insert(1);
-            *transitions += 1;
+            match event {
+                Event::ControllerDeviceAdded {which, ..} => {
+                    match game_controller.open(which as u32) {
+                        Ok(pad) => {
+                            println!("Connected {}", pad.name());
+                            open_controllers.push(pad);
+                        }
+                        Err(e) => println!("Could not open controller {}: {}", which, e),
+                    }
+                }
+                Event::ControllerDeviceRemoved {..} => {
+                    open_controllers.retain(|pad| pad.attached());
+                }
+                _ => (),
+            }
+            if let Some(mut screen) = settings.take() {
+                if let Event::Quit {..} = event {
+                    input.press(Action::Quit);
+                } else if !settings_event(&mut screen, event) {
+                    settings = Some(screen);
+                } else {
+                    match KeyMap::new(screen.bindings().clone()) {
+                        Ok(new_keymap) => {
+                            keymap = new_keymap;
+                            save_bindings(&keymap.bindings, bindings_path);
+                        }
+                        Err(e) => {
+                            screen.show(e);
+                            settings = Some(screen);
+                        }
+                    }
+                }
+                continue;
+            }
+            match event {
+                Event::KeyDown {repeat: false, scancode: Some(Scancode::F1), ..} => {
+                    settings = Some(Settings::new(keymap.bindings.clone()));
+                    // Nothing stays held while the game can not see the keys being let go
+                    input = Input::new();
+                    sources = Sources::new();
+                }
+                Event::Window {win_event_id: WindowEventId::SizeChanged, data1, data2, ..} => {
+                    renderer.resize(data1, data2);
+                }
+                Event::MouseMotion {x, y, ..} if mouse => {
+                    let (width, height) = window.size();
+                    input.aim = Some(asteroids.window_to_world(x as f32,
+                                                               y as f32,
+                                                               width as f32,
+                                                               height as f32));
+                }
+                Event::MouseButtonDown {mouse_btn, ..} if mouse => {
+                    if let Some((source, action)) = translate_mouse_button(mouse_btn) {
+                        sources.set(source, action, true, &mut input);
+                    }
+                }
+                Event::MouseButtonUp {mouse_btn, ..} if mouse => {
+                    if let Some((source, action)) = translate_mouse_button(mouse_btn) {
+                        sources.set(source, action, false, &mut input);
+                    }
+                }
+                Event::Quit {..} => input.press(Action::Quit),
+                Event::KeyDown {repeat: false, scancode: Some(Scancode::F5), ..} => {
+                    quicksave(asteroids);
+                }
+                Event::KeyDown {repeat: false, scancode: Some(Scancode::F12), ..} => {
+                    screenshot(asteroids, tick);
+                }
+                Event::KeyDown {repeat: false, scancode: Some(Scancode::F9), ..} => {
+                    if playback.is_some() || recording.is_some() {
+                        println!("Quickload is disabled while recording or replaying.");
+                    } else {
+                        quickload(asteroids);
+                    }
+                }
+                event => {
+                    if let Some(event) = translate_controller_event(&event) {
+                        asteroids::controller::handle(event,
+                                                      &keymap.bindings,
+                                                      &mut sources,
+                                                      &mut input);
+                        continue;
+                    }
+                    if let Some((source, action, down)) = translate_sdl2_event(event, &keymap) {
+                        sources.set(source, action, down, &mut input);
+                    }
+                }
+            }
+        }
+        if playback.is_some() && input.tapped.contains(&Action::Quit) {
+            break;
+        }
+        reload_tunables(asteroids, watcher.as_mut().map(|w| &mut **w));
+        if let Some(ref screen) = settings {
+            accumulator = 0.0;
+            screen.render(&mut renderer);
+            window.gl_swap_window();
+            continue;
         }
-        asteroids::update_and_render(&mut asteroids, &input, delta);
+        while accumulator >= dt && !finished(asteroids, tick, playback) {
+            step(asteroids, &input, dt, tick, playback, recording.as_mut().map(|r| &mut **r));
+            input.next_tick();
+            accumulator -= dt;
+            tick += 1;
+        }
+        asteroids::asteroids::render(asteroids, accumulator / dt, &mut renderer);
         window.gl_swap_window();
     }
+    tick
+}
+
+fn run_terminal(asteroids: &mut Asteroids,
+                tick_rate: u32,
+                mut watcher: Option<&mut TunablesWatcher>,
+                playback: Option<&Replay>,
+                mut recording: Option<&mut Replay>)
+                -> usize {
+    let terminal = RawTerminal::new();
+    let (columns, rows) = terminal.size();
+    let mut renderer = TerminalRenderer::new(columns, rows);
+    let mut keys = TerminalInput::new();
+
+    let dt = 1.0 / tick_rate as f32;
+    let mut tick = 0;
+    let mut accumulator = 0.0;
+    let mut input = Input::new();
+    let mut current_time = time::precise_time_ns();
+    while !finished(asteroids, tick, playback) {
+        let previous_time = current_time;
+        current_time = time::precise_time_ns();
+        let delta = (current_time - previous_time) as f32 / 1_000_000_000.0;
+        accumulator += delta.min(MAX_FRAME_TIME);
+        keys.poll(tick, &mut input);
+        reload_tunables(asteroids, watcher.as_mut().map(|w| &mut **w));
+        if playback.is_some() && input.tapped.contains(&Action::Quit) {
+            break;
+        }
+        if accumulator < dt {
+            thread::sleep(Duration::from_millis(((dt - accumulator) * 1000.0) as u64));
+            continue;
+        }
+        while accumulator >= dt && !finished(asteroids, tick, playback) {
+            step(asteroids, &input, dt, tick, playback, recording.as_mut().map(|r| &mut **r));
+            input.next_tick();
+            accumulator -= dt;
+            tick += 1;
+        }
+        asteroids::asteroids::render(asteroids, accumulator / dt, &mut renderer);
+    }
+    tick
+}
+
+/// Swaps in the tunables file if it changed, keeping the current tunables if it is broken.
+fn reload_tunables(asteroids: &mut Asteroids, watcher: Option<&mut TunablesWatcher>) {
+    match watcher.and_then(|watcher| watcher.poll()) {
+        Some(Ok(tunables)) => {
+            asteroids.set_tunables(tunables);
+            println!("Reloaded tunables");
+        }
+        Some(Err(e)) => println!("Could not reload tunables: {}", e),
+        None => (),
+    }
+}
+
+fn screenshot(asteroids: &Asteroids, tick: usize) {
+    let mut renderer = SoftwareRenderer::new(800, 600);
+    asteroids::asteroids::render(asteroids, 1.0, &mut renderer);
+    let path = format!("screenshot-{:06}.png", tick);
+    match renderer.framebuffer().save(Path::new(&path)) {
+        Ok(()) => println!("Saved {}", path),
+        Err(e) => println!("Could not save {}: {}", path, e),
+    }
+}
+
+fn quicksave(asteroids: &Asteroids) {
+    match snapshot::save(asteroids, Path::new(QUICKSAVE_PATH)) {
+        Ok(()) => println!("Saved {}", QUICKSAVE_PATH),
+        Err(e) => println!("Could not save {}: {}", QUICKSAVE_PATH, e),
+    }
+}
+
+fn quickload(asteroids: &mut Asteroids) {
+    match snapshot::load(Path::new(QUICKSAVE_PATH)) {
+        Ok(loaded) => {
+            *asteroids = loaded;
+            println!("Loaded {}", QUICKSAVE_PATH);
+        }
+        Err(e) => println!("Could not load {}: {}", QUICKSAVE_PATH, e),
+    }
+}
+
+/// Bindings with their keys resolved to SDL2 scancodes. Controller buttons are looked up by
+/// `Controllers`, so they only need to be valid.
+struct KeyMap {
+    bindings: Bindings,
+    keys: HashMap<Scancode, Action>,
+}
+
+impl KeyMap {
+    fn new(bindings: Bindings) -> Result<KeyMap, String> {
+        let mut keys = HashMap::new();
+        for (binding, &action) in bindings.iter() {
+            let scancode = match *binding {
+                Binding::Key(ref name) => {
+                    Scancode::from_name(name)
+                        .ok_or(format!("Unknown key '{}' for {}.", name, action.name()))?
+                }
+                Binding::Button(ref name) => {
+                    Button::from_string(name)
+                        .ok_or(format!("Unknown controller button '{}' for {}.",
+                                       name,
+                                       action.name()))?;
+                    continue;
+                }
+            };
+            // Different names for the same key are only caught once they are resolved
+            match keys.insert(scancode, action) {
+                Some(other) if other != action => {
+                    return Err(format!("'{}' is bound to both {} and {}.",
+                                       binding,
+                                       other.name(),
+                                       action.name()));
+                }
+                _ => (),
+            }
+        }
+        Ok(KeyMap {
+            bindings: bindings,
+            keys: keys,
+        })
+    }
+}
+
+fn save_bindings(bindings: &Bindings, path: &Path) {
+    match bindings.save(path) {
+        Ok(()) => println!("Saved {:?}", path),
+        Err(e) => println!("Could not save {:?}: {}", path, e),
+    }
+}
+
+/// Passes an SDL2 event to the settings screen, returning whether the screen should close.
+fn settings_event(settings: &mut Settings, event: Event) -> bool {
+    if settings.waiting() {
+        match event {
+            Event::KeyDown {repeat: false, scancode: Some(Scancode::Escape), ..} => {
+                settings.cancel_binding();
+            }
+            Event::KeyDown {repeat: false, scancode: Some(scancode), ..} => {
+                settings.bind(Binding::key(scancode.name()));
+            }
+            Event::ControllerButtonDown {button, ..} => {
+                settings.bind(Binding::button(&button.string()));
+            }
+            _ => (),
+        }
+        return false;
+    }
+    match event {
+        Event::KeyDown {repeat: false, scancode: Some(scancode), ..} => {
+            match scancode {
+                Scancode::Up => settings.previous(),
+                Scancode::Down => settings.next(),
+                Scancode::Return => settings.begin_binding(),
+                Scancode::Backspace | Scancode::Delete => settings.clear(),
+                Scancode::Escape | Scancode::F1 => return true,
+                _ => (),
+            }
+        }
+        _ => (),
+    }
+    false
 }
 
-fn translate_sdl2_event(event: Event) -> char {
+/// Maps an SDL2 key event to the key, its action and whether it was pressed or released.
+fn translate_sdl2_event(event: Event, keymap: &KeyMap) -> Option<(Source, Action, bool)> {
+    let key = |scancode: Scancode| Source::Key(scancode.name().to_lowercase());
     match event {
-        Event::Quit {..} => 'q',
-        Event::KeyDown {repeat: false, scancode: Some(Scancode::Q), ..} => 'q',
-        Event::KeyDown {repeat: false, scancode: Some(Scancode::W), ..} => 'w',
-        Event::KeyDown {repeat: false, scancode: Some(Scancode::A), ..} => 'a',
-        Event::KeyDown {repeat: false, scancode: Some(Scancode::D), ..} => 'd',
-        Event::KeyDown {repeat: false, scancode: Some(Scancode::Space), ..} => ' ',
-        Event::KeyUp {repeat: false, scancode: Some(Scancode::Q), ..} => 'q',
-        Event::KeyUp {repeat: false, scancode: Some(Scancode::W), ..} => 'w',
-        Event::KeyUp {repeat: false, scancode: Some(Scancode::A), ..} => 'a',
-        Event::KeyUp {repeat: false, scancode: Some(Scancode::D), ..} => 'd',
-        Event::KeyUp {repeat: false, scancode: Some(Scancode::Space), ..} => ' ',
-        Event::KeyDown {repeat: false, scancode: Some(Scancode::Up), ..} => 'w',
-        Event::KeyDown {repeat: false, scancode: Some(Scancode::Left), ..} => 'a',
-        Event::KeyDown {repeat: false, scancode: Some(Scancode::Right), ..} => 'd',
-        Event::KeyUp {repeat: false, scancode: Some(Scancode::Up), ..} => 'w',
-        Event::KeyUp {repeat: false, scancode: Some(Scancode::Left), ..} => 'a',
-        Event::KeyUp {repeat: false, scancode: Some(Scancode::Right), ..} => 'd',
-        _ => '§',
+        Event::KeyDown {repeat: false, scancode: Some(scancode), ..} => {
+            keymap.keys.get(&scancode).map(|&action| (key(scancode), action, true))
+        }
+        Event::KeyUp {repeat: false, scancode: Some(scancode), ..} => {
+            keymap.keys.get(&scancode).map(|&action| (key(scancode), action, false))
+        }
+        _ => None,
+    }
+}
+
+/// The action a mouse button triggers when aiming with the mouse.
+fn translate_mouse_button(button: Mouse) -> Option<(Source, Action)> {
+    match button {
+        Mouse::Left => Some((Source::Mouse("left".to_string()), Action::Fire)),
+        Mouse::Right => Some((Source::Mouse("right".to_string()), Action::Thrust)),
+        _ => None,
+    }
+}
+
+/// Maps an SDL2 event to a controller event, for controllers that are already open.
+fn translate_controller_event(event: &Event) -> Option<ControllerEvent> {
+    match *event {
+        Event::ControllerAxisMotion {which, axis, value, ..} => {
+            let axis = match axis {
+                controller::Axis::LeftX => Axis::LeftX,
+                controller::Axis::LeftY => Axis::LeftY,
+                controller::Axis::RightX => Axis::RightX,
+                controller::Axis::RightY => Axis::RightY,
+                controller::Axis::TriggerLeft => Axis::TriggerLeft,
+                controller::Axis::TriggerRight => Axis::TriggerRight,
+            };
+            Some(ControllerEvent::Axis {
+                which: which,
+                axis: axis,
+                value: value,
+            })
+        }
+        Event::ControllerButtonDown {which, button, ..} => {
+            Some(ControllerEvent::Button {
+                which: which,
+                button: button.string(),
+                pressed: true,
+            })
+        }
+        Event::ControllerButtonUp {which, button, ..} => {
+            Some(ControllerEvent::Button {
+                which: which,
+                button: button.string(),
+                pressed: false,
+            })
+        }
+        Event::ControllerDeviceRemoved {which, ..} => {
+            Some(ControllerEvent::Removed { which: which })
+        }
+        _ => None,
     }
 }
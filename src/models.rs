@@ -0,0 +1,76 @@
+use super::font;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Model {
+    PlayerShip,
+    Projectile,
+    Asteroid,
+    Saucer,
+    /// A character of text, see `font::CHARACTERS`.
+    Glyph(char),
+}
+
+pub const MODELS: [Model; 4] = [Model::PlayerShip,
+                                Model::Projectile,
+                                Model::Asteroid,
+                                Model::Saucer];
+
+// Vertices are (x, y, z, w) in a unit square centred on the origin, drawn as a line loop
+const PLAYER_SHIP: [f32; 12] = [
+    0.0, -0.5, 0.0, 1.0,
+    -0.5,  0.5, 0.0, 1.0,
+    0.5,  0.5, 0.0, 1.0,
+];
+
+const PROJECTILE: [f32; 16] = [
+    -0.5, -0.5, 0.0, 1.0,
+    -0.5,  0.5, 0.0, 1.0,
+    0.5,  0.5, 0.0, 1.0,
+    0.5, -0.5, 0.0, 1.0,
+];
+
+const ASTEROID: [f32; 40] = [
+    0.1, -0.5, 0.0, 1.0,
+    -0.4, -0.3, 0.0, 1.0,
+    -0.2, -0.1, 0.0, 1.0,
+    -0.5,  0.0, 0.0, 1.0,
+    -0.4,  0.4, 0.0, 1.0,
+    -0.1,  0.5, 0.0, 1.0,
+    0.3,  0.2, 0.0, 1.0,
+    0.2,  0.1, 0.0, 1.0,
+    0.4, -0.2, 0.0, 1.0,
+    0.4, -0.3, 0.0, 1.0,
+];
+
+// A hull with a dome on top. The lines across the middle are drawn there and back, so that
+// they cancel out when testing which points are inside the outline
+const SAUCER: [f32; 48] = [
+    -0.5,  0.0, 0.0, 1.0,
+    -0.3,  0.2, 0.0, 1.0,
+    0.3,  0.2, 0.0, 1.0,
+    0.5,  0.0, 0.0, 1.0,
+    -0.5,  0.0, 0.0, 1.0,
+    0.5,  0.0, 0.0, 1.0,
+    0.25, -0.1, 0.0, 1.0,
+    -0.25, -0.1, 0.0, 1.0,
+    0.25, -0.1, 0.0, 1.0,
+    0.15, -0.25, 0.0, 1.0,
+    -0.15, -0.25, 0.0, 1.0,
+    -0.25, -0.1, 0.0, 1.0,
+];
+
+impl Model {
+    pub fn vertices(&self) -> &'static [f32] {
+        match *self {
+            Model::PlayerShip => &PLAYER_SHIP,
+            Model::Projectile => &PROJECTILE,
+            Model::Asteroid => &ASTEROID,
+            Model::Saucer => &SAUCER,
+            Model::Glyph(character) => font::glyph(character),
+        }
+    }
+
+    pub fn vertex_count(&self) -> usize {
+        self.vertices().len() / 4
+    }
+}
@@ -0,0 +1,122 @@
+extern crate cgmath;
+
+use cgmath::Vector4;
+
+/// How positions and velocities are stepped forward over a tick.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Integrator {
+    /// Updates the velocity first and moves with the new one. Cheap and stable, but a thrust
+    /// started this tick already moves the ship a full tick's worth.
+    SemiImplicitEuler,
+    /// Moves with the average of the accelerations at the start and end of the tick, which is
+    /// exact for a constant acceleration.
+    VelocityVerlet,
+}
+
+pub const INTEGRATORS: [Integrator; 2] = [Integrator::SemiImplicitEuler,
+                                          Integrator::VelocityVerlet];
+
+impl Integrator {
+    pub fn name(&self) -> &'static str {
+        match *self {
+            Integrator::SemiImplicitEuler => "euler",
+            Integrator::VelocityVerlet => "verlet",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Integrator> {
+        INTEGRATORS.iter().cloned().find(|integrator| integrator.name() == name)
+    }
+
+    /// Steps `position` and `velocity` forward by `dt` under `acceleration`, with linear
+    /// `drag` slowing the velocity by that fraction of itself every second.
+    pub fn integrate(&self,
+                     position: &mut Vector4<f32>,
+                     velocity: &mut Vector4<f32>,
+                     acceleration: Vector4<f32>,
+                     drag: f32,
+                     dt: f32) {
+        match *self {
+            Integrator::SemiImplicitEuler => {
+                *velocity = (acceleration - *velocity * drag) * dt + *velocity;
+                *position = *velocity * dt + *position;
+            }
+            Integrator::VelocityVerlet => {
+                let start = acceleration - *velocity * drag;
+                *position = start * dt * dt * 0.5f32 + *velocity * dt + *position;
+                // Drag at the end of the tick depends on the velocity there, so it is estimated
+                // from the velocity half way through
+                let halfway = start * dt * 0.5f32 + *velocity;
+                let end = acceleration - halfway * drag;
+                *velocity = (start + end) * 0.5f32 * dt + *velocity;
+            }
+        }
+    }
+}
+
+/// Slows `velocity` down to `max_speed` if it is going any faster.
+pub fn cap_speed(velocity: &mut Vector4<f32>, max_speed: f32) {
+    let speed = (velocity.x * velocity.x + velocity.y * velocity.y).sqrt();
+    if speed > max_speed {
+        *velocity = *velocity * (max_speed / speed);
+    }
+}
+
+/// The shorter way to go `d` along an axis of the world that wraps around every `size`.
+pub fn wrap_offset(d: f32, size: f32) -> f32 {
+    if d > size / 2.0 {
+        d - size
+    } else if d < -size / 2.0 {
+        d + size
+    } else {
+        d
+    }
+}
+
+/// Ready-made ways for the ship to handle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Handling {
+    /// Keeps drifting until turned around and thrusted against, up to a top speed.
+    Classic,
+    /// Pulls away harder and coasts to a stop, with a lower top speed.
+    Arcade,
+}
+
+pub const HANDLINGS: [Handling; 2] = [Handling::Classic, Handling::Arcade];
+
+impl Handling {
+    pub fn name(&self) -> &'static str {
+        match *self {
+            Handling::Classic => "classic",
+            Handling::Arcade => "arcade",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Handling> {
+        HANDLINGS.iter().cloned().find(|handling| handling.name() == name)
+    }
+
+    /// Acceleration while thrusting, in pixels per second per second.
+    pub fn thrust(&self) -> f32 {
+        match *self {
+            Handling::Classic => 1000.0,
+            Handling::Arcade => 1500.0,
+        }
+    }
+
+    /// Fraction of its velocity the ship loses every second.
+    pub fn drag(&self) -> f32 {
+        match *self {
+            Handling::Classic => 0.0,
+            Handling::Arcade => 2.0,
+        }
+    }
+
+    /// In pixels per second.
+    pub fn max_speed(&self) -> f32 {
+        match *self {
+            Handling::Classic => 600.0,
+            Handling::Arcade => 400.0,
+        }
+    }
+}
@@ -0,0 +1,262 @@
+use std::collections::BTreeSet;
+use std::fs::File;
+use std::io::BufRead;
+use std::io::BufReader;
+use std::io::Write;
+use std::path::Path;
+use super::input::Action;
+use super::input::Input;
+use super::physics::Handling;
+use super::physics::Integrator;
+use super::tunables::Tunables;
+
+/// Bumped whenever the file format or the simulation changes in a way that breaks old replays.
+pub const VERSION: u32 = 14;
+
+const MAGIC: &'static str = "asteroids-replay";
+
+/// The input passed to every tick of a game, plus what is needed to run that game again.
+pub struct Replay {
+    seed: u64,
+    tick_rate: u32,
+    integrator: Integrator,
+    handling: Handling,
+    tunables: Tunables,
+    inputs: Vec<Input>,
+    checksum: u64,
+}
+
+impl Replay {
+    pub fn new(seed: u64,
+               tick_rate: u32,
+               integrator: Integrator,
+               handling: Handling,
+               tunables: Tunables)
+               -> Replay {
+        Replay {
+            seed: seed,
+            tick_rate: tick_rate,
+            integrator: integrator,
+            handling: handling,
+            tunables: tunables,
+            inputs: Vec::new(),
+            checksum: 0,
+        }
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    pub fn tick_rate(&self) -> u32 {
+        self.tick_rate
+    }
+
+    pub fn integrator(&self) -> Integrator {
+        self.integrator
+    }
+
+    pub fn handling(&self) -> Handling {
+        self.handling
+    }
+
+    pub fn tunables(&self) -> &Tunables {
+        &self.tunables
+    }
+
+    pub fn ticks(&self) -> usize {
+        self.inputs.len()
+    }
+
+    pub fn checksum(&self) -> u64 {
+        self.checksum
+    }
+
+    pub fn input(&self, tick: usize) -> &Input {
+        &self.inputs[tick]
+    }
+
+    pub fn record(&mut self, input: &Input) {
+        self.inputs.push(input.clone());
+    }
+
+    pub fn finish(&mut self, checksum: u64) {
+        self.checksum = checksum;
+    }
+
+    /// Checks that a game played through to the end of the replay finished with `checksum`,
+    /// the way the recorded one did.
+    pub fn verify(&self, checksum: u64) -> Result<(), String> {
+        if checksum == self.checksum {
+            Ok(())
+        } else {
+            Err(format!("Replay diverged: checksum {:016x}, expected {:016x}.",
+                        checksum,
+                        self.checksum))
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        let mut file = File::create(path).map_err(|e| e.to_string())?;
+        let mut out = String::new();
+        out.push_str(&format!("{} {}\n", MAGIC, VERSION));
+        out.push_str(&format!("seed {}\n", self.seed));
+        out.push_str(&format!("tick_rate {}\n", self.tick_rate));
+        out.push_str(&format!("integrator {}\n", self.integrator.name()));
+        out.push_str(&format!("handling {}\n", self.handling.name()));
+        for (name, value) in self.tunables.pairs() {
+            out.push_str(&format!("tunable {} {}\n", name, value));
+        }
+        out.push_str(&format!("ticks {}\n", self.inputs.len()));
+        // Only ticks where something changed are written, what is down and where the mouse
+        // aims carry over to the ticks after it
+        let mut down = BTreeSet::new();
+        let mut aim = None;
+        for (tick, input) in self.inputs.iter().enumerate() {
+            if input.down == down && input.tapped.is_empty() && input.aim == aim {
+                continue;
+            }
+            down = input.down.clone();
+            aim = input.aim;
+            out.push_str(&format!("input {} down", tick));
+            for action in &input.down {
+                out.push_str(&format!(" {}", action.name()));
+            }
+            out.push_str(" tapped");
+            for action in &input.tapped {
+                out.push_str(&format!(" {}", action.name()));
+            }
+            if let Some((x, y)) = input.aim {
+                out.push_str(&format!(" aim {:?} {:?}", x, y));
+            }
+            out.push('\n');
+        }
+        out.push_str(&format!("checksum {:016x}\n", self.checksum));
+        file.write_all(out.as_bytes()).map_err(|e| e.to_string())
+    }
+
+    pub fn load(path: &Path) -> Result<Replay, String> {
+        let file = File::open(path).map_err(|e| e.to_string())?;
+        let mut lines = BufReader::new(file).lines();
+        let mut next_line = || -> Result<String, String> {
+            match lines.next() {
+                Some(line) => line.map_err(|e| e.to_string()),
+                None => Err("Unexpected end of replay file.".to_string()),
+            }
+        };
+
+        let header = next_line()?;
+        let version = parse_field(&header, MAGIC)?;
+        if version != VERSION as u64 {
+            return Err(format!("Unsupported replay version {}, expected {}.", version, VERSION));
+        }
+        let seed = parse_field(&next_line()?, "seed")?;
+        let tick_rate = parse_field(&next_line()?, "tick_rate")? as u32;
+        let line = next_line()?;
+        let integrator = Integrator::from_name(parse_name(&line, "integrator")?)
+                             .ok_or(format!("Unknown integrator in '{}'.", line))?;
+        let line = next_line()?;
+        let handling = Handling::from_name(parse_name(&line, "handling")?)
+                           .ok_or(format!("Unknown handling in '{}'.", line))?;
+        let mut tunables = Tunables::new();
+        let mut line = next_line()?;
+        while line.starts_with("tunable ") {
+            let mut words = line.split_whitespace().skip(1);
+            match (words.next(), words.next()) {
+                (Some(name), Some(value)) => tunables.set(name, value)?,
+                _ => return Err(format!("Invalid tunable in '{}'.", line)),
+            }
+            line = next_line()?;
+        }
+        tunables.validate()?;
+        let ticks = parse_field(&line, "ticks")? as usize;
+
+        let mut inputs = vec![Input::new(); ticks];
+        let mut changed = Vec::new();
+        loop {
+            let line = next_line()?;
+            let mut words = line.split_whitespace();
+            match words.next() {
+                Some("input") => {
+                    let tick = words.next()
+                                    .and_then(|w| w.parse::<usize>().ok())
+                                    .and_then(|t| if t < ticks { Some(t) } else { None })
+                                    .ok_or(format!("Invalid tick in '{}'.", line))?;
+                    let mut input = Input::new();
+                    let mut tapped = false;
+                    while let Some(word) = words.next() {
+                        match word {
+                            "down" => tapped = false,
+                            "tapped" => tapped = true,
+                            "aim" => {
+                                let x = words.next().and_then(|w| w.parse().ok());
+                                let y = words.next().and_then(|w| w.parse().ok());
+                                match (x, y) {
+                                    (Some(x), Some(y)) => input.aim = Some((x, y)),
+                                    _ => return Err(format!("Invalid aim in '{}'.", line)),
+                                }
+                            }
+                            _ => {
+                                let action = Action::from_name(word)
+                                    .ok_or(format!("Invalid action '{}'.", word))?;
+                                if tapped {
+                                    input.tapped.insert(action);
+                                } else {
+                                    input.down.insert(action);
+                                }
+                            }
+                        }
+                    }
+                    changed.push((tick, input));
+                }
+                Some("checksum") => {
+                    let checksum = words.next()
+                                        .and_then(|w| u64::from_str_radix(w, 16).ok())
+                                        .ok_or(format!("Invalid checksum in '{}'.", line))?;
+                    changed.sort_by_key(|&(tick, _)| tick);
+                    let mut changed = changed.into_iter().peekable();
+                    let mut down = BTreeSet::new();
+                    let mut aim = None;
+                    for (tick, input) in inputs.iter_mut().enumerate() {
+                        if changed.peek().map_or(false, |&(t, _)| t == tick) {
+                            let (_, change) = changed.next().unwrap();
+                            down = change.down;
+                            aim = change.aim;
+                            input.tapped = change.tapped;
+                        }
+                        input.down = down.clone();
+                        input.aim = aim;
+                    }
+                    return Ok(Replay {
+                        seed: seed,
+                        tick_rate: tick_rate,
+                        integrator: integrator,
+                        handling: handling,
+                        tunables: tunables,
+                        inputs: inputs,
+                        checksum: checksum,
+                    });
+                }
+                _ => return Err(format!("Unexpected line '{}' in replay file.", line)),
+            }
+        }
+    }
+}
+
+fn parse_field(line: &str, name: &str) -> Result<u64, String> {
+    let mut words = line.split_whitespace();
+    if words.next() != Some(name) {
+        return Err(format!("Expected '{}' but found '{}'.", name, line));
+    }
+    words.next()
+         .and_then(|w| w.parse().ok())
+         .ok_or(format!("Invalid value for '{}' in '{}'.", name, line))
+}
+
+fn parse_name<'a>(line: &'a str, name: &str) -> Result<&'a str, String> {
+    let mut words = line.split_whitespace();
+    if words.next() != Some(name) {
+        return Err(format!("Expected '{}' but found '{}'.", name, line));
+    }
+    words.next().ok_or(format!("Missing value for '{}' in '{}'.", name, line))
+}
@@ -0,0 +1,113 @@
+extern crate cgmath;
+
+use cgmath::Matrix4;
+use super::bindings::Binding;
+use super::bindings::Bindings;
+use super::font;
+use super::input::ACTIONS;
+use super::render;
+use super::render::DrawCommand;
+use super::render::Renderer;
+
+/// The in-game settings screen, where actions are bound to keys and controller buttons.
+///
+/// The frontend decides which of its events move the selection and which become bindings, so
+/// the screen itself only knows about actions and `Binding`s.
+pub struct Settings {
+    bindings: Bindings,
+    projection: Matrix4<f32>,
+    selected: usize,
+    waiting: bool,
+    message: String,
+}
+
+impl Settings {
+    pub fn new(bindings: Bindings) -> Settings {
+        Settings {
+            bindings: bindings,
+            projection: cgmath::ortho(0.0, 800.0, 600.0, 0.0, -1.0, 1.0),
+            selected: 0,
+            waiting: false,
+            message: String::new(),
+        }
+    }
+
+    pub fn bindings(&self) -> &Bindings {
+        &self.bindings
+    }
+
+    /// Whether the next key or button should be bound to the selected action.
+    pub fn waiting(&self) -> bool {
+        self.waiting
+    }
+
+    pub fn previous(&mut self) {
+        self.selected = (self.selected + ACTIONS.len() - 1) % ACTIONS.len();
+        self.message.clear();
+    }
+
+    pub fn next(&mut self) {
+        self.selected = (self.selected + 1) % ACTIONS.len();
+        self.message.clear();
+    }
+
+    pub fn begin_binding(&mut self) {
+        self.waiting = true;
+        self.message = format!("Press a key or button for {}", ACTIONS[self.selected].name());
+    }
+
+    pub fn cancel_binding(&mut self) {
+        self.waiting = false;
+        self.message.clear();
+    }
+
+    /// Adds `binding` to the selected action, or explains why it could not be added.
+    pub fn bind(&mut self, binding: Binding) {
+        self.waiting = false;
+        self.message = match self.bindings.bind(binding, ACTIONS[self.selected]) {
+            Ok(()) => String::new(),
+            Err(e) => e,
+        };
+    }
+
+    /// Removes every binding of the selected action.
+    pub fn clear(&mut self) {
+        self.bindings.unbind(ACTIONS[self.selected]);
+        self.message.clear();
+    }
+
+    /// Shows `message` instead of the current one, for errors found by the frontend.
+    pub fn show(&mut self, message: String) {
+        self.waiting = false;
+        self.message = message;
+    }
+
+    pub fn draw_commands(&self) -> Vec<DrawCommand> {
+        let projection = &self.projection;
+        let mut commands = font::text(projection, "Settings", 40.0, 40.0, 24.0, render::WHITE);
+
+        for (i, action) in ACTIONS.iter().enumerate() {
+            let y = 100.0 + 30.0 * i as f32;
+            if i == self.selected {
+                commands.extend(font::text(projection, ">", 40.0, y, 14.0, render::WHITE));
+            }
+            commands.extend(font::text(projection, action.name(), 70.0, y, 14.0, render::WHITE));
+            let bindings = self.bindings
+                               .bindings(*action)
+                               .iter()
+                               .map(|binding| binding.to_string())
+                               .collect::<Vec<_>>()
+                               .join(", ");
+            commands.extend(font::text(projection, &bindings, 300.0, y, 14.0, render::WHITE));
+        }
+
+        commands.extend(font::text(projection, &self.message, 40.0, 480.0, 14.0, render::WHITE));
+        let help = "Up/Down select, Return add, Backspace clear, Escape close";
+        commands.extend(font::text(projection, help, 40.0, 540.0, 12.0, render::WHITE));
+        commands
+    }
+
+    pub fn render<R: Renderer>(&self, renderer: &mut R) {
+        renderer.draw(&self.draw_commands());
+    }
+}
@@ -0,0 +1,85 @@
+use std::fs::File;
+use std::io::Read;
+use std::io::Write;
+use std::path::Path;
+use std::str::FromStr;
+use super::asteroids::Asteroids;
+
+/// Bumped whenever the snapshot format changes in a way that breaks old snapshots.
+pub const VERSION: u32 = 11;
+
+const MAGIC: &'static str = "asteroids-snapshot";
+
+/// A single `name value...` line of a snapshot.
+pub struct Record<'a> {
+    pub name: &'a str,
+    values: Vec<&'a str>,
+    line: &'a str,
+}
+
+impl<'a> Record<'a> {
+    fn parse(line: &'a str) -> Record<'a> {
+        let mut words = line.split_whitespace();
+        let name = words.next().unwrap_or("");
+        Record {
+            name: name,
+            values: words.collect(),
+            line: line,
+        }
+    }
+
+    pub fn get<T: FromStr>(&self, index: usize) -> Result<T, String> {
+        self.values
+            .get(index)
+            .and_then(|v| v.parse().ok())
+            .ok_or(format!("Invalid value {} in '{}'.", index, self.line))
+    }
+
+    pub fn get_str(&self, index: usize) -> Result<&'a str, String> {
+        self.values
+            .get(index)
+            .map(|v| *v)
+            .ok_or(format!("Missing value {} in '{}'.", index, self.line))
+    }
+
+    pub fn unknown(&self) -> String {
+        format!("Unexpected line '{}' in snapshot.", self.line)
+    }
+}
+
+pub fn write(asteroids: &Asteroids) -> String {
+    let mut out = format!("{} {}\n", MAGIC, VERSION);
+    asteroids.write_snapshot(&mut out);
+    out.push_str("end\n");
+    out
+}
+
+pub fn read(snapshot: &str) -> Result<Asteroids, String> {
+    let mut lines = snapshot.lines().filter(|line| !line.trim().is_empty());
+    let header = Record::parse(lines.next().unwrap_or(""));
+    if header.name != MAGIC {
+        return Err("Not an asteroids snapshot.".to_string());
+    }
+    let version: u32 = header.get(0)?;
+    if version != VERSION {
+        return Err(format!("Unsupported snapshot version {}, expected {}.", version, VERSION));
+    }
+    let mut records = lines.map(Record::parse).collect::<Vec<_>>();
+    // A snapshot that was cut short, say by a full disk, is missing its last line
+    if records.pop().map(|record| record.name) != Some("end") {
+        return Err("Snapshot is truncated.".to_string());
+    }
+    Asteroids::read_snapshot(&records)
+}
+
+pub fn save(asteroids: &Asteroids, path: &Path) -> Result<(), String> {
+    let mut file = File::create(path).map_err(|e| e.to_string())?;
+    file.write_all(write(asteroids).as_bytes()).map_err(|e| e.to_string())
+}
+
+pub fn load(path: &Path) -> Result<Asteroids, String> {
+    let mut file = File::open(path).map_err(|e| e.to_string())?;
+    let mut snapshot = String::new();
+    file.read_to_string(&mut snapshot).map_err(|e| e.to_string())?;
+    read(&snapshot)
+}
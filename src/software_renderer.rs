@@ -0,0 +1,76 @@
+use cgmath::Matrix;
+use cgmath::Vector4;
+use super::framebuffer::Framebuffer;
+use super::render::DrawCommand;
+use super::render::Renderer;
+
+/// Rasterizes line loops on the CPU, for machines without a GPU.
+pub struct SoftwareRenderer {
+    framebuffer: Framebuffer,
+}
+
+impl SoftwareRenderer {
+    pub fn new(width: usize, height: usize) -> SoftwareRenderer {
+        SoftwareRenderer { framebuffer: Framebuffer::new(width, height) }
+    }
+
+    pub fn framebuffer(&self) -> &Framebuffer {
+        &self.framebuffer
+    }
+
+    /// Maps a vertex through `command`'s transform and the viewport to pixel coordinates.
+    fn to_pixel(&self, command: &DrawCommand, vertex: &[f32]) -> (i32, i32) {
+        let v = Vector4::new(vertex[0], vertex[1], vertex[2], vertex[3]);
+        let clip = command.transform.mul_v(v);
+        let (x, y) = (clip.x / clip.w, clip.y / clip.w);
+        let width = self.framebuffer.width() as f32;
+        let height = self.framebuffer.height() as f32;
+        // Clip space y points up, framebuffer rows go down
+        (((x + 1.0) * 0.5 * width).floor() as i32, ((1.0 - y) * 0.5 * height).floor() as i32)
+    }
+
+    fn draw_line(&mut self, from: (i32, i32), to: (i32, i32), colour: [u8; 3]) {
+        // Bresenham's line algorithm
+        let (mut x, mut y) = from;
+        let dx = (to.0 - x).abs();
+        let dy = -(to.1 - y).abs();
+        let sx = if x < to.0 { 1 } else { -1 };
+        let sy = if y < to.1 { 1 } else { -1 };
+        let mut error = dx + dy;
+        loop {
+            self.framebuffer.set(x, y, colour);
+            if (x, y) == to {
+                break;
+            }
+            let e2 = 2 * error;
+            if e2 >= dy {
+                error += dy;
+                x += sx;
+            }
+            if e2 <= dx {
+                error += dx;
+                y += sy;
+            }
+        }
+    }
+}
+
+impl Renderer for SoftwareRenderer {
+    fn draw(&mut self, commands: &[DrawCommand]) {
+        self.framebuffer.clear([0, 0, 0]);
+
+        for command in commands {
+            let colour = [(command.colour[0] * 255.0) as u8,
+                          (command.colour[1] * 255.0) as u8,
+                          (command.colour[2] * 255.0) as u8];
+            let points = command.model
+                                .vertices()
+                                .chunks(4)
+                                .map(|vertex| self.to_pixel(command, vertex))
+                                .collect::<Vec<_>>();
+            for i in 0..points.len() {
+                self.draw_line(points[i], points[(i + 1) % points.len()], colour);
+            }
+        }
+    }
+}
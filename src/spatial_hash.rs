@@ -0,0 +1,70 @@
+/// Buckets points into a grid of cells over a world that wraps around at its edges, so that
+/// only points in neighbouring cells need testing against each other. The grid wraps too, so
+/// the cells along one edge neighbour the cells along the opposite edge.
+pub struct SpatialHash {
+    columns: usize,
+    rows: usize,
+    cell_width: f32,
+    cell_height: f32,
+    cells: Vec<Vec<usize>>,
+}
+
+impl SpatialHash {
+    /// A grid of cells at least `cell_size` across, stretched to fit the world exactly. Points
+    /// closer together than `cell_size` always end up in neighbouring cells.
+    pub fn new(width: f32, height: f32, cell_size: f32) -> SpatialHash {
+        let columns = ((width / cell_size) as usize).max(1);
+        let rows = ((height / cell_size) as usize).max(1);
+        SpatialHash {
+            columns: columns,
+            rows: rows,
+            cell_width: width / columns as f32,
+            cell_height: height / rows as f32,
+            cells: vec![Vec::new(); columns * rows],
+        }
+    }
+
+    /// Adds the point numbered `index` to the cell it lies in.
+    pub fn insert(&mut self, index: usize, x: f32, y: f32) {
+        let column = ((x / self.cell_width).floor() as i64).rem_euclid(self.columns as i64);
+        let row = ((y / self.cell_height).floor() as i64).rem_euclid(self.rows as i64);
+        self.cells[row as usize * self.columns + column as usize].push(index);
+    }
+
+    /// Every pair of points in the same or neighbouring cells, once each with the lower index
+    /// first.
+    pub fn pairs(&self) -> Vec<(usize, usize)> {
+        let mut pairs = Vec::new();
+        let mut neighbours = Vec::with_capacity(9);
+        for row in 0..self.rows {
+            for column in 0..self.columns {
+                let cell = &self.cells[row * self.columns + column];
+                if cell.is_empty() {
+                    continue;
+                }
+                neighbours.clear();
+                for &dy in &[self.rows - 1, 0, 1] {
+                    for &dx in &[self.columns - 1, 0, 1] {
+                        let neighbour_row = (row + dy) % self.rows;
+                        let neighbour_column = (column + dx) % self.columns;
+                        neighbours.push(neighbour_row * self.columns + neighbour_column);
+                    }
+                }
+                // A grid less than three cells across wraps onto the same neighbour twice
+                neighbours.sort();
+                neighbours.dedup();
+                // Each pair is seen from both of its cells, but kept only from the lower index
+                for &a in cell {
+                    for &neighbour in &neighbours {
+                        for &b in &self.cells[neighbour] {
+                            if a < b {
+                                pairs.push((a, b));
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        pairs
+    }
+}
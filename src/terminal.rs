@@ -0,0 +1,215 @@
+use std::collections::HashMap;
+use std::io::Read;
+use std::io::Write;
+use std::io;
+use std::process::Command;
+use std::process::Stdio;
+use std::sync::mpsc::Receiver;
+use std::sync::mpsc;
+use std::thread;
+use super::input::Action;
+use super::input::Input;
+use super::render::DrawCommand;
+use super::render::Renderer;
+use super::software_renderer::SoftwareRenderer;
+
+// Terminals only report key presses and auto-repeats, so a key counts as held until it has not
+// been seen for a while. The first auto-repeat takes longer to arrive than the ones after it.
+const INITIAL_HOLD_TICKS: usize = 30;
+const REPEAT_HOLD_TICKS: usize = 6;
+
+/// Puts the terminal in raw mode with the cursor hidden, and restores it when dropped.
+pub struct RawTerminal {
+    settings: String,
+}
+
+impl RawTerminal {
+    pub fn new() -> RawTerminal {
+        let settings = stty(&["-g"]).expect("Could not read terminal settings.");
+        stty(&["raw", "-echo"]).expect("Could not put terminal in raw mode.");
+        print!("\x1b[2J\x1b[?25l");
+        RawTerminal { settings: settings.trim().to_string() }
+    }
+
+    /// The terminal size as (columns, rows), or 80x24 if it can not be read.
+    pub fn size(&self) -> (usize, usize) {
+        let size = stty(&["size"]).unwrap_or(String::new());
+        let mut words = size.split_whitespace().filter_map(|w| w.parse().ok());
+        match (words.next(), words.next()) {
+            (Some(rows), Some(columns)) => (columns, rows),
+            _ => (80, 24),
+        }
+    }
+}
+
+impl Drop for RawTerminal {
+    fn drop(&mut self) {
+        let _ = stty(&[&self.settings]);
+        print!("\x1b[?25h\x1b[2J\x1b[H");
+        let _ = io::stdout().flush();
+    }
+}
+
+fn stty(args: &[&str]) -> Option<String> {
+    Command::new("stty")
+        .args(args)
+        .stdin(Stdio::inherit())
+        .output()
+        .ok()
+        .and_then(|output| {
+            if output.status.success() {
+                String::from_utf8(output.stdout).ok()
+            } else {
+                None
+            }
+        })
+}
+
+/// Reads keys from stdin on a background thread and turns them into presses and releases of
+/// actions.
+pub struct TerminalInput {
+    keys: Receiver<Action>,
+    // Tick each held action was last seen, and whether it has auto-repeated yet
+    held: HashMap<Action, (usize, bool)>,
+}
+
+impl TerminalInput {
+    pub fn new() -> TerminalInput {
+        let (sender, keys) = mpsc::channel();
+        thread::spawn(move || {
+            let mut stdin = io::stdin();
+            let mut buffer = [0; 64];
+            let mut bytes = Vec::new();
+            loop {
+                let count = match stdin.read(&mut buffer) {
+                    Ok(0) | Err(_) => break,
+                    Ok(count) => count,
+                };
+                bytes.extend_from_slice(&buffer[..count]);
+                for key in decode(&mut bytes) {
+                    if sender.send(key).is_err() {
+                        return;
+                    }
+                }
+            }
+        });
+        TerminalInput {
+            keys: keys,
+            held: HashMap::new(),
+        }
+    }
+
+    /// Applies the keys that arrived since the last poll to `input`.
+    pub fn poll(&mut self, tick: usize, input: &mut Input) {
+        while let Ok(action) = self.keys.try_recv() {
+            // Auto-repeats keep the action held rather than pressing it again
+            if !self.held.contains_key(&action) {
+                input.press(action);
+            }
+            let held = self.held.entry(action).or_insert((tick, false));
+            *held = (tick, held.1 || held.0 != tick);
+        }
+
+        let released = self.held
+                           .iter()
+                           .filter(|&(_, &(seen, repeated))| {
+                               let hold = if repeated {
+                                   REPEAT_HOLD_TICKS
+                               } else {
+                                   INITIAL_HOLD_TICKS
+                               };
+                               tick > seen + hold
+                           })
+                           .map(|(&action, _)| action)
+                           .collect::<Vec<_>>();
+        for action in released {
+            self.held.remove(&action);
+            input.release(action);
+        }
+    }
+}
+
+/// Takes the keys out of the bytes read from the terminal so far, leaving any sequence that has
+/// not fully arrived for the next read.
+pub fn decode(bytes: &mut Vec<u8>) -> Vec<Action> {
+    let mut actions = Vec::new();
+    while let Some((action, length)) = key(bytes) {
+        bytes.drain(..length);
+        actions.extend(action);
+    }
+    // Terminals send a whole sequence in one go, so an escape left over at the end of a read
+    // is the Escape key itself, which quits as it does in a window
+    if bytes[..] == [0x1b] {
+        bytes.clear();
+        actions.push(Action::Quit);
+    }
+    actions
+}
+
+// The action for the key at the start of `bytes` and how many bytes it took, or nothing until
+// enough bytes have arrived to tell
+fn key(bytes: &[u8]) -> Option<(Option<Action>, usize)> {
+    let key = match bytes {
+        [] | [0x1b] | [0x1b, b'['] | [0x1b, b'O'] => return None,
+        // Control sequences run to a final byte from @ to ~. Only the arrow keys are used, as
+        // ESC [ A through ESC [ D, and the rest are skipped.
+        [0x1b, b'[', rest @ ..] => {
+            let end = rest.iter().position(|&b| b >= 0x40 && b <= 0x7e)?;
+            let action = match &rest[..end + 1] {
+                [b'A'] => Some(Action::Thrust),
+                [b'D'] => Some(Action::RotateLeft),
+                [b'C'] => Some(Action::RotateRight),
+                _ => None,
+            };
+            (action, end + 3)
+        }
+        // Function keys on some terminals
+        [0x1b, b'O', _, ..] => (None, 3),
+        // Alt held with a key, which is read as the key alone
+        [0x1b, ..] => (None, 1),
+        [b'w', ..] | [b'W', ..] => (Some(Action::Thrust), 1),
+        [b'a', ..] | [b'A', ..] => (Some(Action::RotateLeft), 1),
+        [b'd', ..] | [b'D', ..] => (Some(Action::RotateRight), 1),
+        [b' ', ..] => (Some(Action::Fire), 1),
+        [b'h', ..] | [b'H', ..] => (Some(Action::Hyperspace), 1),
+        [b'p', ..] | [b'P', ..] => (Some(Action::Pause), 1),
+        // Ctrl-C does not raise a signal in raw mode
+        [b'q', ..] | [b'Q', ..] | [0x03, ..] => (Some(Action::Quit), 1),
+        _ => (None, 1),
+    };
+    Some(key)
+}
+
+/// Draws line loops as characters, with the world scaled to the terminal size.
+pub struct TerminalRenderer {
+    raster: SoftwareRenderer,
+}
+
+impl TerminalRenderer {
+    pub fn new(columns: usize, rows: usize) -> TerminalRenderer {
+        TerminalRenderer { raster: SoftwareRenderer::new(columns, rows) }
+    }
+}
+
+impl Renderer for TerminalRenderer {
+    fn draw(&mut self, commands: &[DrawCommand]) {
+        self.raster.draw(commands);
+        let framebuffer = self.raster.framebuffer();
+
+        // Move home and rewrite every cell rather than clearing, which flickers
+        let mut screen = String::from("\x1b[H");
+        for y in 0..framebuffer.height() {
+            if y > 0 {
+                screen.push_str("\r\n");
+            }
+            for x in 0..framebuffer.width() {
+                let lit = framebuffer.get(x, y) != [0, 0, 0];
+                screen.push(if lit { '*' } else { ' ' });
+            }
+        }
+        let stdout = io::stdout();
+        let mut stdout = stdout.lock();
+        let _ = stdout.write_all(screen.as_bytes());
+        let _ = stdout.flush();
+    }
+}
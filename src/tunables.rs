@@ -0,0 +1,268 @@
+extern crate time;
+
+use std::fs::File;
+use std::fs;
+use std::io::Read;
+use std::io::Write;
+use std::path::Path;
+use std::path::PathBuf;
+use std::time::SystemTime;
+
+// Nanoseconds between looks at the file
+const POLL_INTERVAL: u64 = 500_000_000;
+
+/// The gameplay numbers that can be tuned from a file without recompiling.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Tunables {
+    /// Seconds between shots.
+    pub weapon_cooldown: f32,
+    /// Seconds a projectile flies for.
+    pub projectile_lifetime: f32,
+    /// Pixels per second.
+    pub projectile_speed: f32,
+    /// Degrees per second.
+    pub turn_rate: f32,
+    /// Pixels per second, each asteroid gets a random speed between the two.
+    pub asteroid_min_speed: f32,
+    pub asteroid_max_speed: f32,
+    pub large_asteroid_points: u32,
+    pub medium_asteroid_points: u32,
+    pub small_asteroid_points: u32,
+    /// Points to score for each extra life.
+    pub extra_life_points: u32,
+    pub starting_lives: u32,
+    /// Seconds the ship can not be hit for after losing a life.
+    pub invulnerability_time: f32,
+    /// Seconds the ship is gone for during a hyperspace jump.
+    pub hyperspace_vanish_time: f32,
+    /// Seconds from the start of one hyperspace jump until the next one can start.
+    pub hyperspace_cooldown: f32,
+    /// Chance from 0 to 1 of the ship breaking up when it comes out of hyperspace.
+    pub hyperspace_destruction_chance: f32,
+    /// Seconds between saucers, less `saucer_interval_step` for every stage down to
+    /// `saucer_min_interval`.
+    pub saucer_interval: f32,
+    pub saucer_interval_step: f32,
+    pub saucer_min_interval: f32,
+    /// Pixels per second across the screen.
+    pub saucer_speed: f32,
+    /// Seconds between a saucer's shots.
+    pub saucer_fire_interval: f32,
+    /// Chance of a saucer being a small one for every stage, up to certainty.
+    pub small_saucer_chance: f32,
+    /// Degrees a small saucer's shots can miss by, less `small_saucer_spread_step` for every
+    /// stage down to dead on.
+    pub small_saucer_spread: f32,
+    pub small_saucer_spread_step: f32,
+    pub large_saucer_points: u32,
+    pub small_saucer_points: u32,
+}
+
+enum Value<'a> {
+    Float(&'a mut f32),
+    Integer(&'a mut u32),
+}
+
+impl Tunables {
+    pub fn new() -> Tunables {
+        Tunables {
+            weapon_cooldown: 0.2,
+            projectile_lifetime: 0.75,
+            projectile_speed: 500.0,
+            turn_rate: 300.0,
+            asteroid_min_speed: 100.0,
+            asteroid_max_speed: 200.0,
+            large_asteroid_points: 10,
+            medium_asteroid_points: 25,
+            small_asteroid_points: 50,
+            extra_life_points: 2000,
+            starting_lives: 3,
+            invulnerability_time: 1.0,
+            hyperspace_vanish_time: 0.5,
+            hyperspace_cooldown: 2.0,
+            hyperspace_destruction_chance: 0.1,
+            saucer_interval: 20.0,
+            saucer_interval_step: 2.0,
+            saucer_min_interval: 5.0,
+            saucer_speed: 100.0,
+            saucer_fire_interval: 1.0,
+            small_saucer_chance: 0.1,
+            small_saucer_spread: 30.0,
+            small_saucer_spread_step: 5.0,
+            large_saucer_points: 200,
+            small_saucer_points: 1000,
+        }
+    }
+
+    // Every tunable by name, in the order they are written
+    fn values(&mut self) -> Vec<(&'static str, Value<'_>)> {
+        vec![("weapon_cooldown", Value::Float(&mut self.weapon_cooldown)),
+             ("projectile_lifetime", Value::Float(&mut self.projectile_lifetime)),
+             ("projectile_speed", Value::Float(&mut self.projectile_speed)),
+             ("turn_rate", Value::Float(&mut self.turn_rate)),
+             ("asteroid_min_speed", Value::Float(&mut self.asteroid_min_speed)),
+             ("asteroid_max_speed", Value::Float(&mut self.asteroid_max_speed)),
+             ("large_asteroid_points", Value::Integer(&mut self.large_asteroid_points)),
+             ("medium_asteroid_points", Value::Integer(&mut self.medium_asteroid_points)),
+             ("small_asteroid_points", Value::Integer(&mut self.small_asteroid_points)),
+             ("extra_life_points", Value::Integer(&mut self.extra_life_points)),
+             ("starting_lives", Value::Integer(&mut self.starting_lives)),
+             ("invulnerability_time", Value::Float(&mut self.invulnerability_time)),
+             ("hyperspace_vanish_time", Value::Float(&mut self.hyperspace_vanish_time)),
+             ("hyperspace_cooldown", Value::Float(&mut self.hyperspace_cooldown)),
+             ("hyperspace_destruction_chance",
+              Value::Float(&mut self.hyperspace_destruction_chance)),
+             ("saucer_interval", Value::Float(&mut self.saucer_interval)),
+             ("saucer_interval_step", Value::Float(&mut self.saucer_interval_step)),
+             ("saucer_min_interval", Value::Float(&mut self.saucer_min_interval)),
+             ("saucer_speed", Value::Float(&mut self.saucer_speed)),
+             ("saucer_fire_interval", Value::Float(&mut self.saucer_fire_interval)),
+             ("small_saucer_chance", Value::Float(&mut self.small_saucer_chance)),
+             ("small_saucer_spread", Value::Float(&mut self.small_saucer_spread)),
+             ("small_saucer_spread_step", Value::Float(&mut self.small_saucer_spread_step)),
+             ("large_saucer_points", Value::Integer(&mut self.large_saucer_points)),
+             ("small_saucer_points", Value::Integer(&mut self.small_saucer_points))]
+    }
+
+    /// Sets the tunable called `name` from its text form, unless the game can not run with
+    /// that value. Checks against other tunables wait for `validate`, once all are set.
+    pub fn set(&mut self, name: &str, value: &str) -> Result<(), String> {
+        let mut tunables = self.clone();
+        match tunables.values().into_iter().find(|&(n, _)| n == name) {
+            Some((_, Value::Float(field))) => {
+                *field = value.parse().map_err(|_| {
+                    format!("Invalid number '{}' for {}.", value, name)
+                })?;
+            }
+            Some((_, Value::Integer(field))) => {
+                *field = value.parse().map_err(|_| {
+                    format!("Invalid whole number '{}' for {}.", value, name)
+                })?;
+            }
+            None => return Err(format!("Unknown tunable '{}'.", name)),
+        }
+        tunables.check(name)?;
+        *self = tunables;
+        Ok(())
+    }
+
+    // The limits on a single tunable, whatever the others are
+    fn check(&self, name: &str) -> Result<(), String> {
+        match name {
+            "hyperspace_destruction_chance" => {
+                let chance = self.hyperspace_destruction_chance;
+                if chance < 0.0 || chance > 1.0 {
+                    return Err("hyperspace_destruction_chance is not between 0 and 1.".to_string());
+                }
+            }
+            // Saucers leave once they have crossed the screen, which would take forever
+            "saucer_speed" if self.saucer_speed <= 0.0 => {
+                return Err("saucer_speed is not above 0.".to_string());
+            }
+            _ => (),
+        }
+        Ok(())
+    }
+
+    /// Checks that the game can run with these tunables, each on its own and together.
+    pub fn validate(&self) -> Result<(), String> {
+        for (name, _) in self.pairs() {
+            self.check(name)?;
+        }
+        if self.asteroid_min_speed > self.asteroid_max_speed {
+            return Err("asteroid_min_speed is larger than asteroid_max_speed.".to_string());
+        }
+        Ok(())
+    }
+
+    /// Every tunable as a name and its text form.
+    pub fn pairs(&self) -> Vec<(&'static str, String)> {
+        let mut copy = self.clone();
+        copy.values()
+            .into_iter()
+            .map(|(name, value)| {
+                match value {
+                    Value::Float(field) => (name, format!("{:?}", field)),
+                    Value::Integer(field) => (name, field.to_string()),
+                }
+            })
+            .collect()
+    }
+
+    pub fn write(&self) -> String {
+        let mut out = String::new();
+        for (name, value) in self.pairs() {
+            out.push_str(&format!("{} = {}\n", name, value));
+        }
+        out
+    }
+
+    /// Reads `name = value` lines, where tunables that are left out keep their defaults.
+    pub fn read(text: &str) -> Result<Tunables, String> {
+        let mut tunables = Tunables::new();
+        for (number, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let error = |e: String| format!("Line {}: {}", number + 1, e);
+            let (name, value) = match line.find('=') {
+                Some(i) => (line[..i].trim(), line[i + 1..].trim()),
+                None => return Err(error(format!("Expected 'name = value', found '{}'.", line))),
+            };
+            tunables.set(name, value).map_err(&error)?;
+        }
+        tunables.validate()?;
+        Ok(tunables)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        let mut file = File::create(path).map_err(|e| e.to_string())?;
+        file.write_all(self.write().as_bytes()).map_err(|e| e.to_string())
+    }
+
+    pub fn load(path: &Path) -> Result<Tunables, String> {
+        let mut file = File::open(path).map_err(|e| e.to_string())?;
+        let mut text = String::new();
+        file.read_to_string(&mut text).map_err(|e| e.to_string())?;
+        Tunables::read(&text)
+    }
+}
+
+/// Notices when a tunables file changes, so it can be loaded again while the game runs. The
+/// file is only looked at twice a second, so it is fine to poll every frame.
+pub struct TunablesWatcher {
+    path: PathBuf,
+    modified: Option<SystemTime>,
+    polled: u64,
+}
+
+impl TunablesWatcher {
+    pub fn new(path: &Path) -> TunablesWatcher {
+        TunablesWatcher {
+            path: path.to_path_buf(),
+            modified: modified(path),
+            polled: time::precise_time_ns(),
+        }
+    }
+
+    /// The new tunables, or why they could not be read, if the file changed since the last
+    /// poll.
+    pub fn poll(&mut self) -> Option<Result<Tunables, String>> {
+        let now = time::precise_time_ns();
+        if now - self.polled < POLL_INTERVAL {
+            return None;
+        }
+        self.polled = now;
+        let modified = modified(&self.path);
+        if modified == self.modified || modified.is_none() {
+            return None;
+        }
+        self.modified = modified;
+        Some(Tunables::load(&self.path))
+    }
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|metadata| metadata.modified()).ok()
+}
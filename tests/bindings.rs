@@ -0,0 +1,74 @@
+//! Checks reading and writing bindings files, and that conflicting or reserved bindings are
+//! turned away with an error saying why.
+
+extern crate asteroids;
+
+use asteroids::Action;
+use asteroids::bindings::Binding;
+use asteroids::bindings::Bindings;
+use std::env;
+use std::fs::File;
+use std::io::Write;
+
+#[test]
+fn actions_take_any_number_of_bindings() {
+    let text = "# Comments and blank lines are skipped\n\n\
+                fire = key Space, key Return, button a\n\
+                quit = key Q\n";
+    let bindings = Bindings::read(text).unwrap();
+    assert_eq!(bindings.bindings(Action::Fire),
+               vec![&Binding::key("return"), &Binding::key("space"), &Binding::button("a")]);
+    assert_eq!(bindings.action(&Binding::key("q")), Some(Action::Quit));
+    assert!(bindings.bindings(Action::Thrust).is_empty());
+}
+
+#[test]
+fn names_ignore_case() {
+    let bindings = Bindings::read("thrust = key Left Shift, button DPUP").unwrap();
+    assert_eq!(bindings.action(&Binding::key("left shift")), Some(Action::Thrust));
+    assert_eq!(bindings.action(&Binding::button("dpup")), Some(Action::Thrust));
+}
+
+#[test]
+fn written_bindings_read_back_the_same() {
+    let bindings = Bindings::defaults();
+    assert_eq!(Bindings::read(&bindings.write()).unwrap(), bindings);
+}
+
+#[test]
+fn conflicting_files_are_rejected() {
+    let path = env::temp_dir().join("asteroids-conflicting-bindings.txt");
+    File::create(&path)
+        .unwrap()
+        .write_all(b"thrust = key W\nfire = key Space, key w\n")
+        .unwrap();
+    let error = Bindings::load(&path).unwrap_err();
+    assert_eq!(error, "Line 2: 'key w' is bound to both thrust and fire.");
+}
+
+#[test]
+fn binding_the_same_action_twice_is_no_conflict() {
+    let bindings = Bindings::read("fire = key Space\nfire = key space").unwrap();
+    assert_eq!(bindings.bindings(Action::Fire), vec![&Binding::key("space")]);
+}
+
+#[test]
+fn reserved_keys_are_rejected() {
+    let error = Bindings::read("pause = key P, key F5").unwrap_err();
+    assert_eq!(error, "Line 1: 'key f5' is reserved and can not be bound.");
+    let mut bindings = Bindings::new();
+    assert!(bindings.bind(Binding::key("F12"), Action::Quit).is_err());
+    assert!(bindings.bindings(Action::Quit).is_empty());
+}
+
+#[test]
+fn malformed_lines_are_rejected() {
+    assert_eq!(Bindings::read("thrust key W").unwrap_err(),
+               "Line 1: Expected 'action = bindings', found 'thrust key W'.");
+    assert_eq!(Bindings::read("jump = key J").unwrap_err(),
+               "Line 1: Unknown action 'jump'.");
+    assert_eq!(Bindings::read("fire = mouse left").unwrap_err(),
+               "Line 1: Unknown binding 'mouse left', expected 'key NAME' or 'button NAME'.");
+    assert_eq!(Bindings::read("fire = key").unwrap_err(),
+               "Line 1: Missing name in binding 'key'.");
+}
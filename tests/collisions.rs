@@ -0,0 +1,160 @@
+//! Checks that collisions are reported once per pair of overlapping outlines, with where and
+//! how deep they overlap.
+
+extern crate asteroids;
+extern crate cgmath;
+
+use asteroids::Entity;
+use asteroids::EntityState;
+use asteroids::collisions;
+use asteroids::physics::Handling;
+use asteroids::tunables::Tunables;
+use cgmath::Vector4;
+
+fn at(x: f32, y: f32) -> Vector4<f32> {
+    Vector4::new(x, y, 0.0, 1.0)
+}
+
+// Projectiles are squares 5 pixels across, facing straight up. Medium asteroids are about 25
+// pixels across and solid for at least 5 pixels around their middle, whichever way they face.
+fn projectile(state: &mut EntityState, x: f32, y: f32) -> Entity {
+    Entity::projectile(state, &Tunables::new(), at(x, y), 0.0)
+}
+
+fn asteroid(state: &mut EntityState, x: f32, y: f32) -> Entity {
+    Entity::medium_asteroid(state, &Tunables::new(), at(x, y))
+}
+
+#[test]
+fn entities_do_not_hit_themselves() {
+    let mut state = EntityState::new(1);
+    let entities = [asteroid(&mut state, 100.0, 100.0)];
+    assert!(collisions::find_collisions(&state, &entities).is_empty());
+}
+
+#[test]
+fn pairs_are_reported_once() {
+    let mut state = EntityState::new(1);
+    let entities = [asteroid(&mut state, 100.0, 100.0), projectile(&mut state, 105.0, 100.0)];
+    let collisions = collisions::find_collisions(&state, &entities);
+    assert_eq!(collisions.len(), 1);
+    assert_eq!((collisions[0].a, collisions[0].b), (entities[0].id, entities[1].id));
+}
+
+#[test]
+fn separate_entities_do_not_collide() {
+    let mut state = EntityState::new(1);
+    let entities = [asteroid(&mut state, 100.0, 100.0),
+                    projectile(&mut state, 125.0, 100.0),
+                    asteroid(&mut state, 300.0, 300.0)];
+    assert!(collisions::find_collisions(&state, &entities).is_empty());
+}
+
+#[test]
+fn every_overlapping_pair_is_found() {
+    let mut state = EntityState::new(1);
+    let entities = [asteroid(&mut state, 100.0, 100.0),
+                    asteroid(&mut state, 106.0, 100.0),
+                    asteroid(&mut state, 103.0, 104.0),
+                    projectile(&mut state, 500.0, 500.0)];
+    let pairs = collisions::find_collisions(&state, &entities)
+                    .iter()
+                    .map(|c| (c.a, c.b))
+                    .collect::<Vec<_>>();
+    assert_eq!(pairs, vec![(0, 1), (0, 2), (1, 2)]);
+}
+
+#[test]
+fn contact_is_the_middle_of_the_overlap() {
+    let mut state = EntityState::new(1);
+    let entities = [projectile(&mut state, 100.0, 100.0), projectile(&mut state, 100.0, 103.0)];
+    let collisions = collisions::find_collisions(&state, &entities);
+    // The first reaches down to 102.5 and the second up to 100.5
+    assert!((collisions[0].depth - 2.0).abs() < 0.001, "{}", collisions[0].depth);
+    let contact = collisions[0].contact;
+    assert!((contact.x - 100.0).abs() < 0.001 && (contact.y - 101.5).abs() < 0.001,
+            "{:?}",
+            contact);
+}
+
+#[test]
+fn hyperspace_is_out_of_reach() {
+    let mut state = EntityState::new(1);
+    let entities = [asteroid(&mut state, 100.0, 100.0), projectile(&mut state, 100.0, 100.0)];
+    state.jump_times.insert(entities[1].id, 1.0);
+    assert!(collisions::find_collisions(&state, &entities).is_empty());
+}
+
+#[test]
+fn outlines_inside_each_other_collide() {
+    let mut state = EntityState::new(1);
+    let entities = [asteroid(&mut state, 100.0, 100.0), projectile(&mut state, 100.0, 100.0)];
+    assert_eq!(collisions::find_collisions(&state, &entities).len(), 1);
+}
+
+#[test]
+fn ship_is_only_solid_inside_its_outline() {
+    // The ship points up from (400, 285), and its sides slope out to (390, 315) and (410, 315)
+    let mut state = EntityState::new(1);
+    let ship = Entity::player_ship(&mut state, Handling::Classic);
+    let beside_the_nose = projectile(&mut state, 392.0, 290.0);
+    let on_the_side = projectile(&mut state, 394.0, 300.0);
+    let collisions = collisions::find_collisions(&state, &[ship, beside_the_nose, on_the_side]);
+    let pairs = collisions.iter().map(|c| (c.a, c.b)).collect::<Vec<_>>();
+    assert_eq!(pairs, vec![(ship.id, on_the_side.id)]);
+}
+
+#[test]
+fn fast_entities_hit_what_they_passed_through() {
+    // A long tick carries the projectile from one side of the small asteroid to the other
+    let mut state = EntityState::new(1);
+    let entities = [Entity::small_asteroid(&mut state, &Tunables::new(), at(100.0, 100.0)),
+                    projectile(&mut state, 130.0, 100.0)];
+    state.previous_positions.insert(entities[1].id, at(70.0, 100.0));
+    let collisions = collisions::find_collisions(&state, &entities);
+    assert_eq!(collisions.len(), 1);
+    // It first touched the asteroid on the side it came from
+    assert!(collisions[0].contact.x < 100.0, "{:?}", collisions[0].contact);
+    assert!(collisions[0].depth > 30.0, "{}", collisions[0].depth);
+}
+
+#[test]
+fn entities_that_swapped_sides_met_on_the_way() {
+    // Neither moved further than it is wide, but together they passed through each other
+    let mut state = EntityState::new(1);
+    let entities = [asteroid(&mut state, 120.0, 100.0), asteroid(&mut state, 80.0, 100.0)];
+    state.previous_positions.insert(entities[0].id, at(80.0, 100.0));
+    state.previous_positions.insert(entities[1].id, at(120.0, 100.0));
+    let collisions = collisions::find_collisions(&state, &entities);
+    assert_eq!(collisions.len(), 1);
+    assert!((collisions[0].contact.x - 100.0).abs() < 1.0, "{:?}", collisions[0].contact);
+}
+
+#[test]
+fn entities_moving_together_do_not_meet() {
+    let mut state = EntityState::new(1);
+    let entities = [asteroid(&mut state, 100.0, 100.0), asteroid(&mut state, 140.0, 100.0)];
+    state.previous_positions.insert(entities[0].id, at(40.0, 100.0));
+    state.previous_positions.insert(entities[1].id, at(80.0, 100.0));
+    assert!(collisions::find_collisions(&state, &entities).is_empty());
+}
+
+#[test]
+fn fast_entities_sweep_the_short_way_round() {
+    // Wrapping from the right edge to the left does not cross the middle of the screen
+    let mut state = EntityState::new(1);
+    let entities = [asteroid(&mut state, 400.0, 100.0), projectile(&mut state, 5.0, 100.0)];
+    state.previous_positions.insert(entities[1].id, at(795.0, 100.0));
+    assert!(collisions::find_collisions(&state, &entities).is_empty());
+}
+
+#[test]
+fn shots_hit_what_they_pass_through_on_the_tick_they_are_fired() {
+    // A tenth of a second carries the shot 50 pixels, right over the small asteroid
+    let mut state = EntityState::new(1);
+    let entities = [Entity::small_asteroid(&mut state, &Tunables::new(), at(100.0, 100.0)),
+                    projectile(&mut state, 100.0, 125.0)];
+    entities[1].update(&mut state, 0.1);
+    assert!(state.positions.get(&entities[1].id).unwrap().y < 90.0);
+    assert_eq!(collisions::find_collisions(&state, &entities).len(), 1);
+}
@@ -0,0 +1,145 @@
+//! Drives seeded games from a synthetic game controller and checks that they play out exactly
+//! like the same game driven from the keyboard.
+
+extern crate asteroids;
+
+use asteroids::Action;
+use asteroids::Asteroids;
+use asteroids::Input;
+use asteroids::bindings::Bindings;
+use asteroids::controller::Axis;
+use asteroids::controller::ControllerEvent;
+use asteroids::controller;
+use asteroids::input::Sources;
+
+const SEED: u64 = 7;
+const TICKS: usize = 90;
+const TICK: f32 = 1.0 / 60.0;
+
+/// Runs a seeded game with the controller events in `script` sent before their tick, and
+/// returns the checksum at the end.
+fn run_controller(script: &[(usize, ControllerEvent)]) -> u64 {
+    let bindings = Bindings::defaults();
+    let mut sources = Sources::new();
+    let mut asteroids = Asteroids::new(SEED);
+    let mut input = Input::new();
+    for tick in 1..TICKS + 1 {
+        for &(_, ref event) in script.iter().filter(|&&(t, _)| t == tick) {
+            controller::handle(event.clone(), &bindings, &mut sources, &mut input);
+        }
+        asteroids::update(&mut asteroids, &input, TICK);
+        input.next_tick();
+    }
+    asteroids.checksum()
+}
+
+/// Runs the same game pressing or releasing an action at each `(tick, action, pressed)`.
+fn run_keyboard(script: &[(usize, Action, bool)]) -> u64 {
+    let mut asteroids = Asteroids::new(SEED);
+    let mut input = Input::new();
+    for tick in 1..TICKS + 1 {
+        for &(_, action, pressed) in script.iter().filter(|&&(t, _, _)| t == tick) {
+            if pressed {
+                input.press(action);
+            } else {
+                input.release(action);
+            }
+        }
+        asteroids::update(&mut asteroids, &input, TICK);
+        input.next_tick();
+    }
+    asteroids.checksum()
+}
+
+fn axis(which: i32, axis: Axis, value: i16) -> ControllerEvent {
+    ControllerEvent::Axis {
+        which: which,
+        axis: axis,
+        value: value,
+    }
+}
+
+fn button(which: i32, button: &str, pressed: bool) -> ControllerEvent {
+    ControllerEvent::Button {
+        which: which,
+        button: button.to_string(),
+        pressed: pressed,
+    }
+}
+
+#[test]
+fn stick_rotates_like_keys() {
+    let controller = run_controller(&[(5, axis(0, Axis::LeftX, -32768)),
+                                      (20, axis(0, Axis::LeftX, 0)),
+                                      (30, axis(0, Axis::LeftX, 32767)),
+                                      (40, axis(0, Axis::LeftX, 0))]);
+    let keyboard = run_keyboard(&[(5, Action::RotateLeft, true),
+                                  (20, Action::RotateLeft, false),
+                                  (30, Action::RotateRight, true),
+                                  (40, Action::RotateRight, false)]);
+    assert_eq!(controller, keyboard);
+    assert!(controller != run_keyboard(&[]));
+}
+
+#[test]
+fn trigger_thrusts_like_keys() {
+    let controller = run_controller(&[(10, axis(0, Axis::TriggerRight, 32767)),
+                                      (25, axis(0, Axis::TriggerRight, 0))]);
+    let keyboard = run_keyboard(&[(10, Action::Thrust, true), (25, Action::Thrust, false)]);
+    assert_eq!(controller, keyboard);
+}
+
+#[test]
+fn axes_have_a_dead_zone() {
+    let controller = run_controller(&[(10, axis(0, Axis::TriggerRight, 4000)),
+                                      (15, axis(0, Axis::LeftX, -4000)),
+                                      (20, axis(0, Axis::LeftY, -32768))]);
+    assert_eq!(controller, run_keyboard(&[]));
+}
+
+#[test]
+fn axes_release_below_the_press_threshold() {
+    // Dropping back from fully pressed to just under the press threshold keeps thrusting
+    let controller = run_controller(&[(10, axis(0, Axis::TriggerRight, 32767)),
+                                      (15, axis(0, Axis::TriggerRight, 12000)),
+                                      (20, axis(0, Axis::TriggerRight, 6000))]);
+    let keyboard = run_keyboard(&[(10, Action::Thrust, true), (20, Action::Thrust, false)]);
+    assert_eq!(controller, keyboard);
+}
+
+#[test]
+fn buttons_use_bindings() {
+    let controller = run_controller(&[(10, button(0, "a", true)),
+                                      (12, button(0, "a", false)),
+                                      (20, button(0, "dpleft", true)),
+                                      (30, button(0, "dpleft", false)),
+                                      (40, button(0, "leftstick", true))]);
+    let keyboard = run_keyboard(&[(10, Action::Fire, true),
+                                  (12, Action::Fire, false),
+                                  (20, Action::RotateLeft, true),
+                                  (30, Action::RotateLeft, false)]);
+    assert_eq!(controller, keyboard);
+}
+
+#[test]
+fn unplugging_releases_held_actions() {
+    let controller = run_controller(&[(10, axis(3, Axis::TriggerRight, 32767)),
+                                      (10, button(3, "dpleft", true)),
+                                      (20, ControllerEvent::Removed { which: 3 })]);
+    let keyboard = run_keyboard(&[(10, Action::Thrust, true),
+                                  (10, Action::RotateLeft, true),
+                                  (20, Action::Thrust, false),
+                                  (20, Action::RotateLeft, false)]);
+    assert_eq!(controller, keyboard);
+}
+
+#[test]
+fn controllers_hold_actions_together() {
+    // An action stays down until every controller holding it lets go
+    let controller = run_controller(&[(10, axis(0, Axis::TriggerRight, 32767)),
+                                      (15, axis(1, Axis::TriggerRight, 32767)),
+                                      (20, axis(0, Axis::TriggerRight, 0)),
+                                      (25, ControllerEvent::Removed { which: 1 })]);
+    let keyboard = run_keyboard(&[(10, Action::Thrust, true), (25, Action::Thrust, false)]);
+    assert_eq!(controller, keyboard);
+}
@@ -0,0 +1,120 @@
+//! Checks the collision rules between kinds, and that what they destroy scores once.
+
+extern crate asteroids;
+extern crate cgmath;
+
+use asteroids::Action;
+use asteroids::Asteroids;
+use asteroids::Input;
+use asteroids::Kind;
+use asteroids::SaucerSize;
+use asteroids::Size;
+use asteroids::factions;
+use asteroids::factions::Effect;
+use asteroids::factions::Faction;
+use asteroids::tunables::Tunables;
+use cgmath::Vector4;
+
+const TICK: f32 = 1.0 / 60.0;
+
+const KINDS: [Kind; 8] = [Kind::PlayerShip,
+                          Kind::ProjectileFriendly,
+                          Kind::ProjectileHostile,
+                          Kind::Asteroid(Size::Large),
+                          Kind::Asteroid(Size::Medium),
+                          Kind::Asteroid(Size::Small),
+                          Kind::Saucer(SaucerSize::Large),
+                          Kind::Saucer(SaucerSize::Small)];
+
+#[test]
+fn nothing_hurts_its_own_kind() {
+    for &kind in &KINDS {
+        assert_eq!(factions::effect(kind, kind), Effect::Nothing, "{:?}", kind);
+    }
+}
+
+#[test]
+fn shots_spare_their_own_side() {
+    for &kind in &KINDS {
+        if factions::faction(kind) == Faction::Player {
+            assert_eq!(factions::effect(kind, Kind::ProjectileFriendly), Effect::Nothing);
+        }
+        if factions::faction(kind) == Faction::Alien {
+            assert_eq!(factions::effect(kind, Kind::ProjectileHostile), Effect::Nothing);
+        }
+    }
+}
+
+#[test]
+fn ship_is_damaged_rather_than_destroyed() {
+    for &kind in &KINDS {
+        assert!(factions::effect(Kind::PlayerShip, kind) != Effect::Destroy, "{:?}", kind);
+    }
+    assert_eq!(factions::effect(Kind::PlayerShip, Kind::ProjectileHostile),
+               Effect::Damage);
+}
+
+#[test]
+fn only_targets_score() {
+    let tunables = Tunables::new();
+    assert_eq!(factions::points(Kind::Saucer(SaucerSize::Small), &tunables), 1000);
+    assert_eq!(factions::points(Kind::Asteroid(Size::Medium), &tunables), 25);
+    assert_eq!(factions::points(Kind::ProjectileHostile, &tunables), 0);
+    assert_eq!(factions::points(Kind::PlayerShip, &tunables), 0);
+}
+
+/// The ids of every entity of a kind `is_kind` accepts.
+fn of_kind<F: Fn(Kind) -> bool>(asteroids: &Asteroids, is_kind: F) -> Vec<u32> {
+    asteroids.entities()
+             .iter()
+             .map(|e| e.id)
+             .filter(|id| is_kind(*asteroids.state().kinds.get(id).unwrap()))
+             .collect()
+}
+
+fn is_shot(kind: Kind) -> bool {
+    match kind {
+        Kind::ProjectileFriendly => true,
+        _ => false,
+    }
+}
+
+fn is_asteroid(kind: Kind) -> bool {
+    match kind {
+        Kind::Asteroid(_) => true,
+        _ => false,
+    }
+}
+
+#[test]
+fn targets_hit_twice_in_a_tick_score_once() {
+    let mut asteroids = Asteroids::new(1);
+    let mut input = Input::new();
+    while of_kind(&asteroids, is_asteroid).is_empty() {
+        asteroids::update(&mut asteroids, &input, TICK);
+    }
+    input.press(Action::Fire);
+    // Fire two shots, without waiting for the weapon to cool down
+    while of_kind(&asteroids, is_shot).len() < 2 {
+        let ship = asteroids.entities()[0].id;
+        asteroids.state_mut().weapon_cooldowns.insert(ship, 0.0);
+        asteroids::update(&mut asteroids, &input, TICK);
+    }
+    input.release(Action::Fire);
+
+    // Stop both shots in the middle of an asteroid
+    let target = of_kind(&asteroids, is_asteroid)[0];
+    let kind = *asteroids.state().kinds.get(&target).unwrap();
+    let position = *asteroids.state().positions.get(&target).unwrap();
+    for id in of_kind(&asteroids, is_shot) {
+        let state = asteroids.state_mut();
+        state.positions.insert(id, position);
+        state.previous_positions.insert(id, position);
+        state.velocities.insert(id, Vector4::new(0.0, 0.0, 0.0, 0.0));
+    }
+
+    let score = asteroids.score();
+    asteroids::update(&mut asteroids, &input, TICK);
+    assert!(!asteroids.entities().iter().any(|e| e.id == target));
+    assert_eq!(asteroids.score() - score, factions::points(kind, &Tunables::new()));
+}
@@ -0,0 +1,104 @@
+//! Renders seeded, scripted games with the software renderer and compares selected frames
+//! to the reference images in `tests/golden`.
+//!
+//! Run with `ASTEROIDS_BLESS=1` to write new reference images after an intended change.
+
+extern crate asteroids;
+
+use asteroids::Action;
+use asteroids::Asteroids;
+use asteroids::Input;
+use asteroids::framebuffer::Framebuffer;
+use asteroids::software_renderer::SoftwareRenderer;
+use std::env;
+use std::path::PathBuf;
+
+const WIDTH: usize = 320;
+const HEIGHT: usize = 240;
+const TICK: f32 = 1.0 / 60.0;
+// Channels may differ by this much before a pixel counts as different
+const CHANNEL_TOLERANCE: u8 = 16;
+// Fraction of pixels that may differ before a frame fails
+const PIXEL_TOLERANCE: f32 = 0.002;
+
+/// Runs a seeded game, pressing or releasing an action at each `(tick, action, pressed)` in
+/// `script`, and renders the frame after each tick in `frames`.
+fn run(seed: u64, script: &[(usize, Action, bool)], frames: &[usize]) -> Vec<Framebuffer> {
+    let mut asteroids = Asteroids::new(seed);
+    let mut renderer = SoftwareRenderer::new(WIDTH, HEIGHT);
+    let mut rendered = Vec::new();
+    let mut input = Input::new();
+    let last = *frames.iter().max().unwrap();
+    for tick in 1..last + 1 {
+        for &(_, action, pressed) in script.iter().filter(|&&(t, _, _)| t == tick) {
+            if pressed {
+                input.press(action);
+            } else {
+                input.release(action);
+            }
+        }
+        asteroids::update(&mut asteroids, &input, TICK);
+        input.next_tick();
+        assert!(asteroids.should_continue(), "Game ended at tick {}.", tick);
+        if frames.contains(&tick) {
+            asteroids::asteroids::render(&asteroids, 1.0, &mut renderer);
+            rendered.push(renderer.framebuffer().clone());
+        }
+    }
+    rendered
+}
+
+fn check(name: &str, frame: &Framebuffer) {
+    let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    path.push("tests");
+    path.push("golden");
+    path.push(format!("{}.ppm", name));
+
+    if env::var("ASTEROIDS_BLESS").is_ok() {
+        frame.save(&path).unwrap();
+        return;
+    }
+
+    let expected = Framebuffer::load(&path)
+                       .unwrap_or_else(|e| panic!("Could not load {:?}: {}", path, e));
+    assert_eq!((expected.width(), expected.height()),
+               (frame.width(), frame.height()));
+
+    let mut different = 0;
+    for (a, b) in expected.pixels().chunks(3).zip(frame.pixels().chunks(3)) {
+        if a.iter().zip(b).any(|(a, b)| (*a as i32 - *b as i32).abs() > CHANNEL_TOLERANCE as i32) {
+            different += 1;
+        }
+    }
+    let allowed = (PIXEL_TOLERANCE * (WIDTH * HEIGHT) as f32) as usize;
+    if different > allowed {
+        let actual = env::temp_dir().join(format!("{}.actual.ppm", name));
+        frame.save(&actual).unwrap();
+        panic!("{} differs from {:?} in {} pixels (allowed {}), actual frame written to {:?}.",
+               name,
+               path,
+               different,
+               allowed,
+               actual);
+    }
+}
+
+#[test]
+fn first_stage() {
+    let frames = run(1, &[], &[3, 120]);
+    check("first_stage_spawn", &frames[0]);
+    check("first_stage_drift", &frames[1]);
+}
+
+#[test]
+fn turn_thrust_and_fire() {
+    let script = [(10, Action::RotateLeft, true),
+                  (25, Action::RotateLeft, false),
+                  (25, Action::Thrust, true),
+                  (40, Action::Fire, true),
+                  (45, Action::Thrust, false),
+                  (55, Action::Fire, false)];
+    let frames = run(2, &script, &[30, 60]);
+    check("turn_thrust_and_fire_thrusting", &frames[0]);
+    check("turn_thrust_and_fire_firing", &frames[1]);
+}
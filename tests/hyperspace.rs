@@ -0,0 +1,103 @@
+//! Checks that hyperspace hides the ship, brings it back somewhere else, and respects its
+//! cooldown and destruction chance.
+
+extern crate asteroids;
+
+use asteroids::Action;
+use asteroids::Asteroids;
+use asteroids::Input;
+use asteroids::models::Model;
+use asteroids::tunables::Tunables;
+
+const SEED: u64 = 6;
+const TICK: f32 = 1.0 / 60.0;
+
+/// A game with its ship spawned, that jumps with `tunables`.
+fn start(tunables: Tunables) -> Asteroids {
+    let mut asteroids = Asteroids::with_tunables(SEED, tunables);
+    asteroids::update(&mut asteroids, &Input::new(), TICK);
+    asteroids
+}
+
+fn run(asteroids: &mut Asteroids, ticks: usize) {
+    for _ in 0..ticks {
+        asteroids::update(asteroids, &Input::new(), TICK);
+    }
+}
+
+fn tap_hyperspace(asteroids: &mut Asteroids) {
+    let mut input = Input::new();
+    input.press(Action::Hyperspace);
+    input.release(Action::Hyperspace);
+    asteroids::update(asteroids, &input, TICK);
+}
+
+fn jumping(asteroids: &Asteroids) -> bool {
+    let ship = asteroids.entities()[0].id;
+    asteroids.state().jump_times.contains_key(&ship)
+}
+
+fn safe() -> Tunables {
+    let mut tunables = Tunables::new();
+    tunables.hyperspace_destruction_chance = 0.0;
+    tunables
+}
+
+#[test]
+fn ship_vanishes_and_reappears_elsewhere() {
+    let mut asteroids = start(safe());
+    tap_hyperspace(&mut asteroids);
+    assert!(jumping(&asteroids));
+    // Only the ships showing the lives left are drawn
+    let ships = asteroids::asteroids::draw_commands(&asteroids, 1.0)
+                    .iter()
+                    .filter(|command| command.model == Model::PlayerShip)
+                    .count();
+    assert_eq!(ships as u32, asteroids.lives());
+
+    run(&mut asteroids, 30);
+    assert!(!jumping(&asteroids));
+    let ship = asteroids.entities()[0].id;
+    let position = asteroids.state().positions.get(&ship).unwrap();
+    assert!(position.x != 400.0 || position.y != 300.0);
+}
+
+#[test]
+fn hyperspace_has_a_cooldown() {
+    let mut asteroids = start(safe());
+    tap_hyperspace(&mut asteroids);
+    run(&mut asteroids, 30);
+    tap_hyperspace(&mut asteroids);
+    assert!(!jumping(&asteroids));
+    run(&mut asteroids, 90);
+    tap_hyperspace(&mut asteroids);
+    assert!(jumping(&asteroids));
+}
+
+#[test]
+fn reentry_can_destroy_the_ship() {
+    let mut tunables = Tunables::new();
+    tunables.hyperspace_destruction_chance = 1.0;
+    let mut asteroids = start(tunables);
+    let lives = asteroids.lives();
+    tap_hyperspace(&mut asteroids);
+    run(&mut asteroids, 30);
+    assert_eq!(asteroids.lives(), lives - 1);
+}
+
+#[test]
+fn ship_can_not_be_hit_in_hyperspace() {
+    let mut tunables = safe();
+    tunables.hyperspace_vanish_time = 5.0;
+    // Without jumping, an asteroid runs into the ship
+    let mut asteroids = start(tunables.clone());
+    let lives = asteroids.lives();
+    run(&mut asteroids, 300);
+    assert!(asteroids.lives() < lives);
+
+    let mut asteroids = start(tunables);
+    tap_hyperspace(&mut asteroids);
+    run(&mut asteroids, 290);
+    assert!(jumping(&asteroids));
+    assert_eq!(asteroids.lives(), lives);
+}
@@ -0,0 +1,143 @@
+//! Checks the pressed, held and released edges that ticks see when a frontend loses, repeats
+//! or squeezes key events into a single tick, and when several keys and buttons hold the same
+//! action.
+
+extern crate asteroids;
+
+use asteroids::Action;
+use asteroids::Asteroids;
+use asteroids::Input;
+use asteroids::controller::Control;
+use asteroids::input::Actions;
+use asteroids::input::Source;
+use asteroids::input::Sources;
+use std::collections::BTreeSet;
+
+const TICK: f32 = 1.0 / 60.0;
+
+/// Feeds key events to an `Input` a tick at a time, the way a frontend and the game do.
+struct Ticks {
+    input: Input,
+    previous: BTreeSet<Action>,
+}
+
+impl Ticks {
+    fn new() -> Ticks {
+        Ticks {
+            input: Input::new(),
+            previous: BTreeSet::new(),
+        }
+    }
+
+    /// The edges of a tick with `events` reported during it, true for a key down and false
+    /// for a key up.
+    fn tick(&mut self, events: &[(Action, bool)]) -> Actions {
+        for &(action, down) in events {
+            if down {
+                self.input.press(action);
+            } else {
+                self.input.release(action);
+            }
+        }
+        let actions = Actions::new(&self.previous, &self.input);
+        self.previous = self.input.down.clone();
+        self.input.next_tick();
+        actions
+    }
+}
+
+fn edges(actions: &Actions, action: Action) -> (bool, bool, bool) {
+    (actions.pressed(action), actions.held(action), actions.released(action))
+}
+
+#[test]
+fn pressing_again_after_a_lost_key_up_counts() {
+    let mut ticks = Ticks::new();
+    assert_eq!(edges(&ticks.tick(&[(Action::Pause, true)]), Action::Pause),
+               (true, true, false));
+    // The key up went missing, so the key still looks held
+    assert_eq!(edges(&ticks.tick(&[]), Action::Pause), (false, true, false));
+    // The next key down is a fresh press, rather than being swallowed by the stuck one
+    assert_eq!(edges(&ticks.tick(&[(Action::Pause, true)]), Action::Pause),
+               (true, true, false));
+    assert_eq!(edges(&ticks.tick(&[(Action::Pause, false)]), Action::Pause),
+               (false, false, true));
+    assert_eq!(edges(&ticks.tick(&[]), Action::Pause), (false, false, false));
+}
+
+#[test]
+fn a_duplicate_key_down_presses_once() {
+    let mut ticks = Ticks::new();
+    let actions = ticks.tick(&[(Action::Fire, true), (Action::Fire, true)]);
+    assert_eq!(edges(&actions, Action::Fire), (true, true, false));
+    assert_eq!(edges(&ticks.tick(&[]), Action::Fire), (false, true, false));
+    assert_eq!(edges(&ticks.tick(&[(Action::Fire, false)]), Action::Fire),
+               (false, false, true));
+}
+
+#[test]
+fn a_tap_within_a_tick_is_pressed_held_and_released() {
+    let mut ticks = Ticks::new();
+    let actions = ticks.tick(&[(Action::Hyperspace, true), (Action::Hyperspace, false)]);
+    assert_eq!(edges(&actions, Action::Hyperspace), (true, true, true));
+    assert_eq!(edges(&ticks.tick(&[]), Action::Hyperspace), (false, false, false));
+}
+
+#[test]
+fn a_lost_key_up_does_not_stick_the_pause_toggle() {
+    let mut asteroids = Asteroids::new(1);
+    let mut input = Input::new();
+    asteroids::update(&mut asteroids, &input, TICK);
+
+    input.press(Action::Pause);
+    asteroids::update(&mut asteroids, &input, TICK);
+    input.next_tick();
+    assert!(asteroids.paused());
+    // Without the key up, the next key down still unpauses
+    asteroids::update(&mut asteroids, &input, TICK);
+    assert!(asteroids.paused());
+    input.press(Action::Pause);
+    asteroids::update(&mut asteroids, &input, TICK);
+    input.next_tick();
+    assert!(!asteroids.paused());
+}
+
+#[test]
+fn actions_stay_down_until_every_source_lets_go() {
+    let mut sources = Sources::new();
+    let mut input = Input::new();
+    let key = Source::Key("space".to_string());
+    let mouse = Source::Mouse("left".to_string());
+    let button = Source::Controller(0, Control::Button("a".to_string()));
+
+    sources.set(key.clone(), Action::Fire, true, &mut input);
+    sources.set(mouse.clone(), Action::Fire, true, &mut input);
+    sources.set(button.clone(), Action::Fire, true, &mut input);
+    input.next_tick();
+    sources.set(key.clone(), Action::Fire, false, &mut input);
+    sources.set(button.clone(), Action::Fire, false, &mut input);
+    assert!(input.down.contains(&Action::Fire));
+    // Another source pressing the held action is not a fresh press
+    sources.set(key.clone(), Action::Fire, true, &mut input);
+    assert!(input.tapped.is_empty());
+    sources.set(key, Action::Fire, false, &mut input);
+    sources.set(mouse, Action::Fire, false, &mut input);
+    assert!(!input.down.contains(&Action::Fire));
+}
+
+#[test]
+fn a_source_pressing_again_presses_again() {
+    let mut sources = Sources::new();
+    let mut input = Input::new();
+    let key = Source::Key("p".to_string());
+    sources.set(key.clone(), Action::Pause, true, &mut input);
+    input.next_tick();
+    // The key up went missing, so this key down is a fresh press
+    sources.set(key.clone(), Action::Pause, true, &mut input);
+    assert!(input.tapped.contains(&Action::Pause));
+    sources.set(key.clone(), Action::Pause, false, &mut input);
+    assert!(!input.down.contains(&Action::Pause));
+    // Letting go of a key that isn't held changes nothing
+    sources.set(key, Action::Pause, false, &mut input);
+    assert!(!input.down.contains(&Action::Pause));
+}
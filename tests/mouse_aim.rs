@@ -0,0 +1,47 @@
+//! Checks that the mouse maps to the same world position at any window size, and that aiming
+//! turns the ship towards it.
+
+extern crate asteroids;
+
+use asteroids::Asteroids;
+use asteroids::Input;
+
+const TICK: f32 = 1.0 / 60.0;
+
+#[test]
+fn window_maps_to_world_at_any_size() {
+    let asteroids = Asteroids::new(1);
+    for &(width, height) in &[(800.0, 600.0), (1920.0, 1080.0), (333.0, 777.0)] {
+        let (x, y) = asteroids.window_to_world(0.0, 0.0, width, height);
+        assert!(x.abs() < 0.01 && y.abs() < 0.01, "{:?} at {}x{}", (x, y), width, height);
+        let (x, y) = asteroids.window_to_world(width * 0.75, height * 0.5, width, height);
+        assert!((x - 600.0).abs() < 0.01 && (y - 300.0).abs() < 0.01,
+                "{:?} at {}x{}",
+                (x, y),
+                width,
+                height);
+    }
+}
+
+/// The ship's direction after aiming at `aim` for `ticks` ticks.
+fn direction_after(aim: (f32, f32), ticks: usize) -> f32 {
+    let mut asteroids = Asteroids::new(1);
+    let mut input = Input::new();
+    input.aim = Some(aim);
+    for _ in 0..ticks {
+        asteroids::update(&mut asteroids, &input, TICK);
+    }
+    let ship = asteroids.entities()[0].id;
+    *asteroids.state().directions.get(&ship).unwrap()
+}
+
+#[test]
+fn ship_turns_towards_aim() {
+    // The ship starts in the middle of the world facing up
+    assert!((direction_after((600.0, 300.0), 60) - 90.0).abs() < 0.01);
+    assert!((direction_after((200.0, 300.0), 60) + 90.0).abs() < 0.01);
+    assert!((direction_after((400.0, 100.0), 60)).abs() < 0.01);
+    // It turns gradually rather than snapping
+    let partway = direction_after((600.0, 300.0), 5);
+    assert!(partway > 0.0 && partway < 90.0);
+}
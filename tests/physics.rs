@@ -0,0 +1,122 @@
+//! Checks the integrators and turning against motion that can be worked out by hand.
+
+extern crate asteroids;
+extern crate cgmath;
+
+use asteroids::Action;
+use asteroids::Asteroids;
+use asteroids::Entity;
+use asteroids::EntityState;
+use asteroids::Input;
+use asteroids::physics;
+use asteroids::physics::Integrator;
+use asteroids::physics::INTEGRATORS;
+use asteroids::tunables::Tunables;
+use cgmath::Vector4;
+
+const TICK: f32 = 1.0 / 60.0;
+
+/// Position and velocity along x after `ticks` ticks from rest.
+fn run(integrator: Integrator, acceleration: f32, drag: f32, ticks: usize) -> (f32, f32) {
+    let mut position = Vector4::new(0.0, 0.0, 0.0, 1.0);
+    let mut velocity = Vector4::new(0.0, 0.0, 0.0, 0.0);
+    let acceleration = Vector4::new(acceleration, 0.0, 0.0, 0.0);
+    for _ in 0..ticks {
+        integrator.integrate(&mut position, &mut velocity, acceleration, drag, TICK);
+    }
+    (position.x, velocity.x)
+}
+
+#[test]
+fn verlet_is_exact_for_constant_acceleration() {
+    // One second at 100 pixels per second per second covers 50 pixels
+    let (position, velocity) = run(Integrator::VelocityVerlet, 100.0, 0.0, 60);
+    assert!((position - 50.0).abs() < 0.01, "{}", position);
+    assert!((velocity - 100.0).abs() < 0.01, "{}", velocity);
+}
+
+#[test]
+fn euler_overshoots_constant_acceleration() {
+    let (position, velocity) = run(Integrator::SemiImplicitEuler, 100.0, 0.0, 60);
+    assert!(position > 50.5, "{}", position);
+    assert!((velocity - 100.0).abs() < 0.01, "{}", velocity);
+}
+
+#[test]
+fn drag_approaches_terminal_velocity() {
+    // Acceleration and drag balance at acceleration / drag
+    for integrator in &INTEGRATORS {
+        let (_, velocity) = run(*integrator, 100.0, 2.0, 600);
+        assert!((velocity - 50.0).abs() < 0.5,
+                "{} reached {}",
+                integrator.name(),
+                velocity);
+    }
+}
+
+#[test]
+fn speed_is_capped_in_any_direction() {
+    let mut velocity = Vector4::new(300.0, -400.0, 0.0, 0.0);
+    physics::cap_speed(&mut velocity, 100.0);
+    assert!((velocity.x - 60.0).abs() < 0.001 && (velocity.y + 80.0).abs() < 0.001);
+    physics::cap_speed(&mut velocity, 200.0);
+    assert!((velocity.x - 60.0).abs() < 0.001 && (velocity.y + 80.0).abs() < 0.001);
+}
+
+/// Which way the ship faces.
+fn heading(asteroids: &Asteroids) -> f32 {
+    *asteroids.state().directions.get(&asteroids.entities()[0].id).unwrap()
+}
+
+#[test]
+fn ship_turns_at_the_turn_rate_at_any_tick_rate() {
+    let turn_rate = Tunables::new().turn_rate;
+    for &ticks_per_second in &[30, 60, 144] {
+        let dt = 1.0 / ticks_per_second as f32;
+        let mut asteroids = Asteroids::new(1);
+        let mut input = Input::new();
+        asteroids::update(&mut asteroids, &input, dt);
+        let start = heading(&asteroids);
+
+        // Half a second turning right, then letting go
+        input.press(Action::RotateRight);
+        for _ in 0..ticks_per_second / 2 {
+            asteroids::update(&mut asteroids, &input, dt);
+            input.next_tick();
+        }
+        let turned = heading(&asteroids) - start;
+        let expected = turn_rate * (ticks_per_second / 2) as f32 * dt;
+        assert!((turned - expected).abs() < 0.01,
+                "Turned {} at {} ticks a second",
+                turned,
+                ticks_per_second);
+
+        input.release(Action::RotateRight);
+        asteroids::update(&mut asteroids, &input, dt);
+        let stopped = heading(&asteroids);
+        for _ in 0..ticks_per_second {
+            asteroids::update(&mut asteroids, &input, dt);
+        }
+        assert_eq!(heading(&asteroids), stopped);
+        let ship = asteroids.entities()[0].id;
+        assert_eq!(*asteroids.state().angular_velocities.get(&ship).unwrap(), 0.0);
+    }
+}
+
+#[test]
+fn angular_acceleration_integrates_exactly() {
+    // One second from 10 degrees a second at 30 degrees a second per second
+    let mut state = EntityState::new(1);
+    let tunables = Tunables::new();
+    let asteroid = Entity::large_asteroid(&mut state, &tunables);
+    state.directions.insert(asteroid.id, 0.0);
+    state.angular_velocities.insert(asteroid.id, 10.0);
+    state.angular_accelerations.insert(asteroid.id, 30.0);
+    for _ in 0..60 {
+        asteroid.update(&mut state, TICK);
+    }
+    let direction = *state.directions.get(&asteroid.id).unwrap();
+    let angular_velocity = *state.angular_velocities.get(&asteroid.id).unwrap();
+    assert!((direction - 25.0).abs() < 0.01, "{}", direction);
+    assert!((angular_velocity - 40.0).abs() < 0.01, "{}", angular_velocity);
+}
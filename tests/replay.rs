@@ -0,0 +1,133 @@
+//! Checks that a recorded game saves, loads and plays back to the same end, and that replays
+//! that were tampered with or are from another version are turned away.
+
+extern crate asteroids;
+
+use asteroids::Action;
+use asteroids::Asteroids;
+use asteroids::Input;
+use asteroids::physics::Handling;
+use asteroids::physics::Integrator;
+use asteroids::replay;
+use asteroids::replay::Replay;
+use asteroids::tunables::Tunables;
+use std::env;
+use std::fs::File;
+use std::io::Read;
+use std::io::Write;
+use std::path::PathBuf;
+
+const SEED: u64 = 11;
+const TICK_RATE: u32 = 60;
+const TICKS: usize = 600;
+
+/// A game set up the way `replay` was recorded.
+fn game(replay: &Replay) -> Asteroids {
+    let mut asteroids = Asteroids::with_tunables(replay.seed(), replay.tunables().clone());
+    asteroids.set_integrator(replay.integrator());
+    asteroids.set_handling(replay.handling());
+    asteroids
+}
+
+/// Records a game of turning, thrusting and firing.
+fn record() -> (Replay, Asteroids) {
+    let mut replay = Replay::new(SEED,
+                                 TICK_RATE,
+                                 Integrator::VelocityVerlet,
+                                 Handling::Arcade,
+                                 Tunables::new());
+    let mut asteroids = game(&replay);
+    let mut input = Input::new();
+    for tick in 0..TICKS {
+        match tick {
+            // Asteroids only come once the ship is alone
+            30 => input.press(Action::Fire),
+            60 => input.press(Action::RotateLeft),
+            400 => input.release(Action::RotateLeft),
+            450 => input.press(Action::Thrust),
+            500 => input.release(Action::Thrust),
+            550 => input.aim = Some((100.0, 100.0)),
+            _ => (),
+        }
+        if !asteroids.should_continue() {
+            break;
+        }
+        replay.record(&input);
+        asteroids::update(&mut asteroids, &input, 1.0 / TICK_RATE as f32);
+        input.next_tick();
+    }
+    replay.finish(asteroids.checksum());
+    (replay, asteroids)
+}
+
+fn play(replay: &Replay) -> Asteroids {
+    let mut asteroids = game(replay);
+    for tick in 0..replay.ticks() {
+        asteroids::update(&mut asteroids, replay.input(tick), 1.0 / replay.tick_rate() as f32);
+    }
+    asteroids
+}
+
+/// Saves `replay` to a file called `name`, and returns the file's path and contents.
+fn save(replay: &Replay, name: &str) -> (PathBuf, String) {
+    let path = env::temp_dir().join(name);
+    replay.save(&path).expect("Could not save the replay");
+    let mut text = String::new();
+    File::open(&path).unwrap().read_to_string(&mut text).unwrap();
+    (path, text)
+}
+
+fn rewrite(path: &PathBuf, text: &str) {
+    File::create(path).unwrap().write_all(text.as_bytes()).unwrap();
+}
+
+#[test]
+fn saved_replays_play_back_the_same() {
+    let (replay, recorded) = record();
+    let (path, _) = save(&replay, "asteroids-round-trip-replay.txt");
+    let loaded = Replay::load(&path).expect("Could not load the replay");
+    assert_eq!(loaded.ticks(), replay.ticks());
+    assert_eq!(loaded.checksum(), recorded.checksum());
+    for tick in 0..replay.ticks() {
+        assert_eq!(loaded.input(tick), replay.input(tick), "Tick {}", tick);
+    }
+    let played = play(&loaded);
+    assert_eq!(played.checksum(), recorded.checksum());
+    assert_eq!(loaded.verify(played.checksum()), Ok(()));
+    assert!(recorded.score() > 0);
+}
+
+#[test]
+fn corrupted_checksums_are_rejected() {
+    let (replay, recorded) = record();
+    let (path, text) = save(&replay, "asteroids-corrupted-replay.txt");
+    let checksum = format!("checksum {:016x}", recorded.checksum());
+    assert!(text.contains(&checksum));
+
+    rewrite(&path, &text.replace(&checksum, "checksum not-a-checksum"));
+    assert_eq!(Replay::load(&path).err(),
+               Some("Invalid checksum in 'checksum not-a-checksum'.".to_string()));
+
+    let wrong = recorded.checksum() ^ 1;
+    rewrite(&path, &text.replace(&checksum, &format!("checksum {:016x}", wrong)));
+    let loaded = Replay::load(&path).expect("Could not load the replay");
+    let played = play(&loaded);
+    assert_eq!(loaded.verify(played.checksum()),
+               Err(format!("Replay diverged: checksum {:016x}, expected {:016x}.",
+                           recorded.checksum(),
+                           wrong)));
+}
+
+#[test]
+fn other_versions_are_rejected() {
+    let (replay, _) = record();
+    let (path, text) = save(&replay, "asteroids-other-version-replay.txt");
+    let header = format!("asteroids-replay {}\n", replay::VERSION);
+    assert!(text.starts_with(&header));
+    let other = format!("asteroids-replay {}\n", replay::VERSION + 1);
+    rewrite(&path, &text.replacen(&header, &other, 1));
+    assert_eq!(Replay::load(&path).err(),
+               Some(format!("Unsupported replay version {}, expected {}.",
+                            replay::VERSION + 1,
+                            replay::VERSION)));
+}
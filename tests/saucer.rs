@@ -0,0 +1,117 @@
+//! Checks that saucers turn up on their timer, fly across the screen and shoot the way their
+//! size says.
+
+extern crate asteroids;
+
+use asteroids::Asteroids;
+use asteroids::Input;
+use asteroids::Kind;
+use asteroids::SaucerSize;
+use asteroids::tunables::Tunables;
+
+const SEED: u64 = 3;
+const TICK: f32 = 1.0 / 60.0;
+
+fn run(asteroids: &mut Asteroids, ticks: usize) {
+    for _ in 0..ticks {
+        asteroids::update(asteroids, &Input::new(), TICK);
+    }
+}
+
+/// Ids of the entities of a kind matching `filter`.
+fn find<F: Fn(Kind) -> bool>(asteroids: &Asteroids, filter: F) -> Vec<u32> {
+    asteroids.entities()
+             .iter()
+             .map(|e| e.id)
+             .filter(|id| filter(*asteroids.state().kinds.get(id).unwrap()))
+             .collect()
+}
+
+fn saucers(asteroids: &Asteroids) -> Vec<u32> {
+    find(asteroids, |kind| match kind {
+        Kind::Saucer(_) => true,
+        _ => false,
+    })
+}
+
+fn tunables(small_saucer_chance: f32) -> Tunables {
+    let mut tunables = Tunables::new();
+    tunables.saucer_interval = 1.0;
+    tunables.small_saucer_chance = small_saucer_chance;
+    tunables.small_saucer_spread = 0.0;
+    tunables
+}
+
+#[test]
+fn saucer_arrives_and_leaves() {
+    let mut asteroids = Asteroids::with_tunables(SEED, tunables(0.0));
+    run(&mut asteroids, 50);
+    assert!(saucers(&asteroids).is_empty());
+    run(&mut asteroids, 20);
+    let saucer = saucers(&asteroids);
+    assert_eq!(saucer.len(), 1);
+    match *asteroids.state().kinds.get(&saucer[0]).unwrap() {
+        Kind::Saucer(SaucerSize::Large) => (),
+        kind => panic!("Expected a large saucer, got {:?}", kind),
+    }
+    // Crossing the screen takes eight seconds, and the next one comes a second later
+    run(&mut asteroids, 8 * 60);
+    assert!(!saucers(&asteroids).contains(&saucer[0]));
+}
+
+#[test]
+fn saucer_zigzags() {
+    let mut asteroids = Asteroids::with_tunables(SEED, tunables(0.0));
+    run(&mut asteroids, 61);
+    let saucer = saucers(&asteroids)[0];
+    let y = asteroids.state().positions.get(&saucer).unwrap().y;
+    run(&mut asteroids, 60);
+    assert_eq!(asteroids.state().positions.get(&saucer).unwrap().y, y);
+    run(&mut asteroids, 120);
+    assert!(asteroids.state().positions.get(&saucer).unwrap().y != y);
+}
+
+#[test]
+fn small_saucer_aims_at_the_ship() {
+    let mut asteroids = Asteroids::with_tunables(SEED, tunables(1.0));
+    run(&mut asteroids, 61);
+    let saucer = saucers(&asteroids)[0];
+    match *asteroids.state().kinds.get(&saucer).unwrap() {
+        Kind::Saucer(SaucerSize::Small) => (),
+        kind => panic!("Expected a small saucer, got {:?}", kind),
+    }
+    // It fires a second after arriving
+    run(&mut asteroids, 65);
+    let shots = find(&asteroids, |kind| match kind {
+        Kind::ProjectileHostile => true,
+        _ => false,
+    });
+    assert_eq!(shots.len(), 1);
+    // The ship sits still in the middle, so the shot flies straight at it
+    let ship = asteroids.state().positions.get(&asteroids.entities()[0].id).unwrap();
+    let shot = asteroids.state().positions.get(&shots[0]).unwrap();
+    let velocity = asteroids.state().velocities.get(&shots[0]).unwrap();
+    let (dx, dy) = (ship.x - shot.x, ship.y - shot.y);
+    let cross = (dx * velocity.y - dy * velocity.x) / (dx.hypot(dy) * velocity.x.hypot(velocity.y));
+    assert!(cross.abs() < 0.001, "Missed by {}", cross);
+    assert!(dx * velocity.x + dy * velocity.y > 0.0);
+}
+
+#[test]
+fn saucer_leaves_over_the_far_edge_at_any_speed() {
+    let mut asteroids = Asteroids::with_tunables(SEED, tunables(0.0));
+    run(&mut asteroids, 61);
+    let saucer = saucers(&asteroids)[0];
+    // Speeding up mid-flight brings it to the far edge in half the time that is left
+    let mut faster = asteroids.tunables().clone();
+    faster.saucer_speed *= 2.0;
+    asteroids.set_tunables(faster);
+    let mut ticks = 0;
+    while saucers(&asteroids).contains(&saucer) {
+        let x = asteroids.state().positions.get(&saucer).unwrap().x;
+        assert!(x > 0.0 && x < 800.0, "Still flying at {}", x);
+        run(&mut asteroids, 1);
+        ticks += 1;
+    }
+    assert!(ticks > 3 * 60 && ticks < 5 * 60, "Flew for {} ticks", ticks);
+}
@@ -0,0 +1,177 @@
+//! Checks that a snapshot of a game in full swing loads back into the same game, which then
+//! plays on exactly as the original does, and that broken snapshots are turned away.
+
+extern crate asteroids;
+
+use asteroids::Action;
+use asteroids::Asteroids;
+use asteroids::Input;
+use asteroids::snapshot;
+use asteroids::tunables::Tunables;
+use std::env;
+
+// The ship scores, and is hit and still invulnerable by the end
+const SEED: u64 = 13;
+const TICKS: usize = 340;
+const TICK: f32 = 1.0 / 60.0;
+
+// Every record a snapshot holds
+const RECORDS: [&'static str; 32] = ["stage",
+                                     "score",
+                                     "lives",
+                                     "live_up",
+                                     "invulnerability_time",
+                                     "saucer_time",
+                                     "tunable",
+                                     "handling",
+                                     "paused",
+                                     "held",
+                                     "entities",
+                                     "rng",
+                                     "entity_count",
+                                     "integrator",
+                                     "kind",
+                                     "acceleration",
+                                     "position",
+                                     "previous_position",
+                                     "velocity",
+                                     "drag",
+                                     "max_speed",
+                                     "direction",
+                                     "previous_direction",
+                                     "angular_velocity",
+                                     "angular_acceleration",
+                                     "model",
+                                     "scale",
+                                     "lifetime",
+                                     "weapon_cooldown",
+                                     "hyperspace_cooldown",
+                                     "jump_time",
+                                     "end"];
+
+fn run(asteroids: &mut Asteroids, input: &mut Input, ticks: usize) {
+    for _ in 0..ticks {
+        asteroids::update(asteroids, input, TICK);
+        input.next_tick();
+    }
+}
+
+/// The values of the first `name` record in `snapshot`.
+fn value(snapshot: &str, name: &str) -> String {
+    snapshot.lines()
+            .map(|line| line.split_whitespace().collect::<Vec<_>>())
+            .find(|words| words[0] == name)
+            .map(|words| words[1..].join(" "))
+            .unwrap_or_else(|| panic!("No {} in the snapshot.", name))
+}
+
+/// A game with shots in flight, the ship mid-jump and an asteroid tumbling faster and faster,
+/// so that every record is in its snapshot.
+fn busy_game() -> (Asteroids, Input) {
+    let mut tunables = Tunables::new();
+    tunables.saucer_interval = 2.0;
+    let mut asteroids = Asteroids::with_tunables(SEED, tunables);
+    let mut input = Input::new();
+    input.press(Action::Fire);
+    input.press(Action::RotateLeft);
+    run(&mut asteroids, &mut input, TICKS);
+
+    input.press(Action::Hyperspace);
+    input.release(Action::Hyperspace);
+    run(&mut asteroids, &mut input, 1);
+
+    let asteroid = asteroids.entities()[1].id;
+    asteroids.state_mut().angular_accelerations.insert(asteroid, 30.0);
+    (asteroids, input)
+}
+
+#[test]
+fn snapshots_hold_every_record() {
+    let (asteroids, _) = busy_game();
+    let snapshot = snapshot::write(&asteroids);
+    for name in &RECORDS {
+        value(&snapshot, name);
+    }
+    assert!(asteroids.score() > 0);
+    assert!(value(&snapshot, "live_up") != "0");
+    assert!(value(&snapshot, "invulnerability_time").parse::<f32>().unwrap() > 0.0);
+    assert!(value(&snapshot, "entity_count").parse::<usize>().unwrap() >
+            asteroids.entities().len());
+}
+
+#[test]
+fn snapshots_read_back_the_same() {
+    let (asteroids, _) = busy_game();
+    let path = env::temp_dir().join("asteroids-round-trip-snapshot.txt");
+    snapshot::save(&asteroids, &path).expect("Could not save the snapshot");
+    let loaded = snapshot::load(&path).expect("Could not load the snapshot");
+    assert_eq!(snapshot::write(&loaded), snapshot::write(&asteroids));
+    assert_eq!(loaded.checksum(), asteroids.checksum());
+}
+
+#[test]
+fn loaded_games_play_on_the_same() {
+    let (mut asteroids, mut input) = busy_game();
+    let mut loaded = snapshot::read(&snapshot::write(&asteroids))
+                         .expect("Could not read the snapshot");
+    let mut loaded_input = input.clone();
+    run(&mut asteroids, &mut input, 120);
+    run(&mut loaded, &mut loaded_input, 120);
+    assert_eq!(loaded.checksum(), asteroids.checksum());
+    assert_eq!(snapshot::write(&loaded), snapshot::write(&asteroids));
+}
+
+/// `snapshot` without the lines `keep` turns down.
+fn without<F: Fn(&str) -> bool>(snapshot: &str, keep: F) -> String {
+    snapshot.lines().filter(|line| keep(line)).map(|line| format!("{}\n", line)).collect()
+}
+
+#[test]
+fn truncated_snapshots_are_rejected() {
+    let (asteroids, _) = busy_game();
+    let snapshot = snapshot::write(&asteroids);
+    let lines = snapshot.lines().count();
+    for &cut in &[lines / 4, lines / 2, lines - 1] {
+        let truncated = snapshot.lines().take(cut).collect::<Vec<_>>().join("\n");
+        assert_eq!(snapshot::read(&truncated).err(),
+                   Some("Snapshot is truncated.".to_string()));
+    }
+}
+
+#[test]
+fn snapshots_missing_what_a_kind_needs_are_rejected() {
+    let (asteroids, _) = busy_game();
+    let snapshot = snapshot::write(&asteroids);
+    let ship = asteroids.entities()[0].id;
+    for name in &["acceleration", "angular_velocity", "weapon_cooldown", "hyperspace_cooldown"] {
+        let record = format!("{} {} ", name, ship);
+        let broken = without(&snapshot, |line| !line.starts_with(&record));
+        assert_eq!(snapshot::read(&broken).err(),
+                   Some(format!("Entity {} in snapshot is missing components.", ship)));
+    }
+
+    let mut tunables = Tunables::new();
+    tunables.saucer_interval = 0.0;
+    let mut asteroids = Asteroids::with_tunables(SEED, tunables);
+    run(&mut asteroids, &mut Input::new(), 2);
+    let saucer = asteroids.entities().last().unwrap().id;
+    let snapshot = snapshot::write(&asteroids);
+    assert!(snapshot.contains(&format!("kind {} saucer_", saucer)));
+    let record = format!("weapon_cooldown {} ", saucer);
+    let broken = without(&snapshot, |line| !line.starts_with(&record));
+    assert_eq!(snapshot::read(&broken).err(),
+               Some(format!("Entity {} in snapshot is missing components.", saucer)));
+}
+
+#[test]
+fn snapshots_must_start_with_the_ship() {
+    let (asteroids, _) = busy_game();
+    let snapshot = snapshot::write(&asteroids);
+    let ids = asteroids.entities().iter().map(|e| e.id.to_string()).collect::<Vec<_>>();
+    let mut swapped = ids.clone();
+    swapped.swap(0, 1);
+    let broken = snapshot.replace(&format!("entities {}", ids.join(" ")),
+                                  &format!("entities {}", swapped.join(" ")));
+    assert_eq!(snapshot::read(&broken).err(),
+               Some("The first entity in snapshot is not the ship.".to_string()));
+}
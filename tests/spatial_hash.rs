@@ -0,0 +1,69 @@
+//! Checks that the spatial hash finds every pair of nearby points, across the edges of the
+//! world too, and no pair twice.
+
+extern crate asteroids;
+
+use asteroids::spatial_hash::SpatialHash;
+
+const WIDTH: f32 = 800.0;
+const HEIGHT: f32 = 600.0;
+
+/// Distance between two points, the short way round the world.
+fn wrapped_distance(a: (f32, f32), b: (f32, f32)) -> f32 {
+    let dx = (a.0 - b.0).abs();
+    let dy = (a.1 - b.1).abs();
+    let dx = dx.min(WIDTH - dx);
+    let dy = dy.min(HEIGHT - dy);
+    (dx * dx + dy * dy).sqrt()
+}
+
+fn pairs(points: &[(f32, f32)], cell_size: f32) -> Vec<(usize, usize)> {
+    let mut grid = SpatialHash::new(WIDTH, HEIGHT, cell_size);
+    for (index, &(x, y)) in points.iter().enumerate() {
+        grid.insert(index, x, y);
+    }
+    grid.pairs()
+}
+
+#[test]
+fn nearby_points_are_paired_once() {
+    // Scatter points with a simple generator so the test needs no random numbers
+    let mut seed: u32 = 1;
+    let mut next = || {
+        seed = seed.wrapping_mul(1103515245).wrapping_add(12345);
+        (seed >> 8) as f32 / (1 << 24) as f32
+    };
+    let points = (0..500).map(|_| (next() * WIDTH, next() * HEIGHT)).collect::<Vec<_>>();
+    let cell_size = 30.0;
+    let mut found = pairs(&points, cell_size);
+    let count = found.len();
+    found.sort();
+    found.dedup();
+    assert_eq!(found.len(), count, "Some pairs were found twice.");
+    for (i, &a) in points.iter().enumerate() {
+        for (j, &b) in points.iter().enumerate().skip(i + 1) {
+            if wrapped_distance(a, b) < cell_size {
+                assert!(found.binary_search(&(i, j)).is_ok(), "Missed {:?} and {:?}", a, b);
+            }
+        }
+    }
+}
+
+#[test]
+fn pairs_wrap_around_the_edges() {
+    let points = [(1.0, 300.0), (799.0, 300.0), (400.0, 1.0), (400.0, 599.0), (0.0, 0.0)];
+    let found = pairs(&points, 50.0);
+    assert!(found.contains(&(0, 1)));
+    assert!(found.contains(&(2, 3)));
+    assert!(!found.contains(&(0, 2)));
+}
+
+#[test]
+fn small_grids_pair_once() {
+    // Two cells across wraps onto the same neighbour from both sides
+    let points = [(100.0, 100.0), (500.0, 400.0), (700.0, 100.0)];
+    let found = pairs(&points, 400.0);
+    assert_eq!(found, vec![(0, 1), (0, 2), (1, 2)]);
+    let found = pairs(&points, 1000.0);
+    assert_eq!(found, vec![(0, 1), (0, 2), (1, 2)]);
+}
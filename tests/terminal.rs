@@ -0,0 +1,48 @@
+//! Checks that keys read from a terminal turn into the right actions, with escape sequences
+//! for keys the game does not use skipped.
+
+extern crate asteroids;
+
+use asteroids::Action;
+use asteroids::terminal;
+
+fn decode(bytes: &[u8]) -> (Vec<Action>, Vec<u8>) {
+    let mut bytes = bytes.to_vec();
+    let actions = terminal::decode(&mut bytes);
+    (actions, bytes)
+}
+
+#[test]
+fn keys_and_arrows_become_actions() {
+    assert_eq!(decode(b"w \x1b[D\x1b[Cq").0,
+               vec![Action::Thrust,
+                    Action::Fire,
+                    Action::RotateLeft,
+                    Action::RotateRight,
+                    Action::Quit]);
+}
+
+#[test]
+fn down_and_unknown_sequences_are_skipped() {
+    // Down, Home, F5 and the start of a bracketed paste
+    assert_eq!(decode(b"\x1b[B\x1b[H\x1b[15~\x1b[200~h"),
+               (vec![Action::Hyperspace], vec![]));
+    assert_eq!(decode(b"\x1bOPp"), (vec![Action::Pause], vec![]));
+}
+
+#[test]
+fn escape_alone_quits() {
+    assert_eq!(decode(b"\x1b"), (vec![Action::Quit], vec![]));
+    // Followed by a key, it is Alt with the key
+    assert_eq!(decode(b"\x1bp"), (vec![Action::Pause], vec![]));
+}
+
+#[test]
+fn partial_sequences_wait_for_the_rest() {
+    let mut bytes = b"\x1b[1;5".to_vec();
+    assert!(terminal::decode(&mut bytes).is_empty());
+    assert_eq!(bytes, b"\x1b[1;5".to_vec());
+    bytes.extend_from_slice(b"B\x1b[A");
+    assert_eq!(terminal::decode(&mut bytes), vec![Action::Thrust]);
+    assert!(bytes.is_empty());
+}
@@ -0,0 +1,80 @@
+//! Checks that tunables files read back what was written, that values the game can not run
+//! with are turned away however they are set, and that tuned games play out differently.
+
+extern crate asteroids;
+
+use asteroids::Asteroids;
+use asteroids::Input;
+use asteroids::snapshot;
+use asteroids::tunables::Tunables;
+
+const TICK: f32 = 1.0 / 60.0;
+
+#[test]
+fn tunables_round_trip() {
+    let mut tunables = Tunables::new();
+    tunables.weapon_cooldown = 0.1;
+    tunables.starting_lives = 5;
+    assert_eq!(Tunables::read(&tunables.write()), Ok(tunables));
+}
+
+#[test]
+fn missing_tunables_keep_defaults() {
+    let tunables = Tunables::read("# Faster shots\nprojectile_speed = 800\n").unwrap();
+    assert_eq!(tunables.projectile_speed, 800.0);
+    assert_eq!(tunables.weapon_cooldown, Tunables::new().weapon_cooldown);
+}
+
+#[test]
+fn bad_tunables_name_the_line() {
+    assert_eq!(Tunables::read("\nstarting_lives = 2.5\n"),
+               Err("Line 2: Invalid whole number '2.5' for starting_lives.".to_string()));
+    assert_eq!(Tunables::read("shields = 1\n"),
+               Err("Line 1: Unknown tunable 'shields'.".to_string()));
+    assert!(Tunables::read("asteroid_min_speed = 300\n").is_err());
+}
+
+#[test]
+fn set_checks_like_read() {
+    let mut tunables = Tunables::new();
+    assert_eq!(tunables.set("saucer_speed", "0"),
+               Err("saucer_speed is not above 0.".to_string()));
+    assert_eq!(tunables.set("hyperspace_destruction_chance", "1.5"),
+               Err("hyperspace_destruction_chance is not between 0 and 1.".to_string()));
+    // Rejected values are not kept
+    assert_eq!(tunables, Tunables::new());
+    assert_eq!(Tunables::read("saucer_speed = 0\n"),
+               Err("Line 1: saucer_speed is not above 0.".to_string()));
+}
+
+#[test]
+fn snapshots_with_bad_tunables_are_rejected() {
+    let text = snapshot::write(&Asteroids::new(1));
+    let speed = format!("tunable saucer_speed {:?}\n", Tunables::new().saucer_speed);
+    assert!(text.contains(&speed));
+    assert_eq!(snapshot::read(&text.replace(&speed, "tunable saucer_speed 0\n")).err(),
+               Some("saucer_speed is not above 0.".to_string()));
+
+    let min_speed = format!("tunable asteroid_min_speed {:?}\n",
+                            Tunables::new().asteroid_min_speed);
+    assert!(text.contains(&min_speed));
+    assert_eq!(snapshot::read(&text.replace(&min_speed, "tunable asteroid_min_speed 300\n"))
+                   .err(),
+               Some("asteroid_min_speed is larger than asteroid_max_speed.".to_string()));
+}
+
+#[test]
+fn tunables_change_the_game() {
+    let run = |tunables: Tunables| {
+        let mut asteroids = Asteroids::with_tunables(3, tunables);
+        let input = Input::new();
+        for _ in 0..60 {
+            asteroids::update(&mut asteroids, &input, TICK);
+        }
+        asteroids.checksum()
+    };
+    let mut slow = Tunables::new();
+    slow.asteroid_max_speed = 150.0;
+    assert_eq!(run(Tunables::new()), run(Tunables::new()));
+    assert!(run(Tunables::new()) != run(slow));
+}
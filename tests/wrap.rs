@@ -0,0 +1,70 @@
+//! Checks that entities meet across the edges of the world, and are drawn on both sides of an
+//! edge they stick out over.
+
+extern crate asteroids;
+extern crate cgmath;
+
+use asteroids::Asteroids;
+use asteroids::Entity;
+use asteroids::EntityState;
+use asteroids::Input;
+use asteroids::collisions;
+use asteroids::models::Model;
+use asteroids::tunables::Tunables;
+use cgmath::Vector4;
+
+fn at(x: f32, y: f32) -> Vector4<f32> {
+    Vector4::new(x, y, 0.0, 1.0)
+}
+
+/// A game with its ship spawned and standing still at (`x`, `y`).
+fn ship_at(x: f32, y: f32) -> Asteroids {
+    let mut asteroids = Asteroids::new(1);
+    asteroids::update(&mut asteroids, &Input::new(), 1.0 / 60.0);
+    asteroids.place_ship(at(x, y), Vector4::new(0.0, 0.0, 0.0, 0.0));
+    asteroids
+}
+
+fn ships_drawn(asteroids: &Asteroids) -> usize {
+    let ships = asteroids::asteroids::draw_commands(asteroids, 1.0)
+                    .iter()
+                    .filter(|command| command.model == Model::PlayerShip)
+                    .count();
+    // Less the ships showing the lives left
+    ships - asteroids.lives() as usize
+}
+
+#[test]
+fn entities_collide_across_edges() {
+    let tunables = Tunables::new();
+    let mut state = EntityState::new(1);
+    let entities = [Entity::medium_asteroid(&mut state, &tunables, at(798.0, 300.0)),
+                    Entity::projectile(&mut state, &tunables, at(2.0, 300.0), 0.0),
+                    Entity::medium_asteroid(&mut state, &tunables, at(400.0, 598.0)),
+                    Entity::projectile(&mut state, &tunables, at(400.0, 2.0), 0.0)];
+    let collisions = collisions::find_collisions(&state, &entities);
+    let pairs = collisions.iter().map(|c| (c.a, c.b)).collect::<Vec<_>>();
+    assert_eq!(pairs, vec![(0, 1), (2, 3)]);
+    for collision in &collisions {
+        let contact = collision.contact;
+        assert!(contact.x >= 0.0 && contact.x < 800.0 && contact.y >= 0.0 && contact.y < 600.0,
+                "{:?}",
+                contact);
+    }
+}
+
+#[test]
+fn entities_in_the_middle_have_no_ghosts() {
+    assert_eq!(ships_drawn(&ship_at(400.0, 300.0)), 1);
+}
+
+#[test]
+fn entities_over_an_edge_are_drawn_on_both_sides() {
+    assert_eq!(ships_drawn(&ship_at(5.0, 300.0)), 2);
+    assert_eq!(ships_drawn(&ship_at(400.0, 595.0)), 2);
+}
+
+#[test]
+fn entities_over_a_corner_are_drawn_in_every_corner() {
+    assert_eq!(ships_drawn(&ship_at(795.0, 5.0)), 4);
+}